@@ -0,0 +1,431 @@
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+const VOLUME_WORDS: [&str; 4] = ["том", "т.", "vol", "vol."];
+const CHAPTER_WORDS: [&str; 5] = ["глава", "гл.", "гл", "chapter", "ch."];
+const PART_WORDS: [&str; 2] = ["часть", "ч."];
+const EXTRA_WORDS: [&str; 7] = [
+	"экстра",
+	"бонус",
+	"спешл",
+	"extra",
+	"bonus",
+	"special",
+	"omake",
+];
+// chapter paths also abbreviate extras, like `vol0/ex2`
+const PATH_EXTRA_WORDS: [&str; 1] = ["ex"];
+const PROLOGUE_WORDS: [&str; 2] = ["пролог", "prologue"];
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ChapterLabel {
+	pub volume: Option<f32>,
+	// for extras it is their own number, like 3 of "Экстра 3", see `number_extras`
+	pub chapter: Option<f32>,
+	pub is_extra: bool,
+	// what is left of the label after the numbering words are removed
+	pub title: String,
+}
+
+fn normalize_word(word: &str) -> String {
+	word.trim_matches(|c: char| matches!(c, '(' | ')' | '[' | ']' | ',' | ':' | ';'))
+		.to_lowercase()
+}
+
+// Accepts "25", "25.5", "25,5" and ranges like "10-11", in which case the first number is taken
+fn parse_number(word: &str) -> Option<f32> {
+	let word = normalize_word(word);
+	let number = word
+		.split(['-', '–'])
+		.next()?
+		.trim_end_matches('.')
+		.replace(',', ".");
+	if number.starts_with(|c: char| c.is_ascii_digit()) {
+		number.parse().ok()
+	} else {
+		None
+	}
+}
+
+// Handles both "Том 3" and "Том3"
+fn parse_labeled_number(words: &[&str], idx: usize, labels: &[&str]) -> Option<(f32, usize)> {
+	let word = normalize_word(words[idx]);
+	for label in labels {
+		if word == *label {
+			return words
+				.get(idx + 1)
+				.and_then(|next_word| parse_number(next_word))
+				.map(|number| (number, 2));
+		}
+		if let Some(number) = word.strip_prefix(label).and_then(parse_number) {
+			return Some((number, 1));
+		}
+	}
+	None
+}
+
+fn is_whole(number: f32) -> bool {
+	number == (number as i64) as f32
+}
+
+// Parses labels like "Том 3 Глава 25 - Начало", "2 - 15 Начало", "Гл. 10-11",
+// "Глава 100 (часть 2)", "Экстра 3" or "Пролог"; numbers without any label are taken only
+// from the label start. Extras keep their word and number in the title
+pub fn parse_chapter_label(label: &str) -> ChapterLabel {
+	let words: Vec<&str> = label.split_whitespace().collect();
+	let mut consumed = Vec::from_iter(words.iter().map(|_| false));
+	let mut result = ChapterLabel::default();
+	let mut part = None;
+	let mut extra_number = None;
+
+	let mut idx = 0;
+	while idx < words.len() {
+		let word = normalize_word(words[idx]);
+		// extra words count only where the numbering is, "Глава 12 - Бонусный уровень" is a title
+		let is_numbering_position = consumed[..idx].iter().all(|consumed| *consumed);
+		let mut consume = |number: f32, count: usize, target: &mut Option<f32>| {
+			if target.is_none() {
+				*target = Some(number);
+			}
+			consumed[idx..idx + count].fill(true);
+			count
+		};
+
+		let step = if let Some((number, count)) = parse_labeled_number(&words, idx, &VOLUME_WORDS) {
+			consume(number, count, &mut result.volume)
+		} else if let Some((number, count)) = parse_labeled_number(&words, idx, &CHAPTER_WORDS) {
+			consume(number, count, &mut result.chapter)
+		} else if let Some((number, count)) = parse_labeled_number(&words, idx, &PART_WORDS) {
+			consume(number, count, &mut part)
+		} else if let Some((number, count)) =
+			parse_labeled_number(&words, idx, &EXTRA_WORDS).filter(|_| is_numbering_position)
+		{
+			result.is_extra = true;
+			extra_number = extra_number.or(Some(number));
+			count
+		} else {
+			if is_numbering_position && EXTRA_WORDS.contains(&word.as_str()) {
+				result.is_extra = true;
+			} else if PROLOGUE_WORDS.contains(&word.as_str()) && result.chapter.is_none() {
+				result.chapter = Some(0.0);
+			}
+			1
+		};
+		idx += step;
+	}
+
+	if result.chapter.is_none() && !result.is_extra {
+		// "2 - 15 Начало" is volume 2 chapter 15
+		let leading_numbers = match words[..] {
+			[volume, "-", chapter, ..] => parse_number(volume).zip(parse_number(chapter)),
+			_ => None,
+		};
+		if let Some((volume, chapter)) = leading_numbers {
+			result.volume = result.volume.or(Some(volume));
+			result.chapter = Some(chapter);
+			consumed[..3].fill(true);
+		} else if let Some(number) = words.first().and_then(|word| parse_number(word)) {
+			result.chapter = Some(number);
+			consumed[0] = true;
+		}
+	}
+
+	// "Глава 100 (часть 2)" becomes 100.02, a part without a chapter is the chapter itself. Parts
+	// from 10 on are left out, part 10 would be 100.1 and take the place of "Глава 100.1"
+	result.chapter = match (result.chapter, part) {
+		(Some(chapter), Some(part)) if is_whole(chapter) && is_whole(part) && part < 10.0 => {
+			Some(chapter + part / 100.0)
+		}
+		(None, Some(part)) if !result.is_extra => Some(part),
+		(chapter, _) => chapter,
+	};
+	if result.is_extra {
+		result.chapter = extra_number.or(result.chapter);
+	}
+
+	let rest: Vec<&str> = words
+		.iter()
+		.zip(consumed)
+		.filter(|(_, consumed)| !consumed)
+		.map(|(word, _)| *word)
+		.collect();
+	result.title = rest
+		.join(" ")
+		.trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '–' | '—' | ':' | '.' | ','))
+		.to_string();
+	if result.title == "()" {
+		result.title.clear();
+	}
+	result
+}
+
+// Leading decimal number of the string, `,` is accepted as decimal separator
+fn parse_leading_number(s: &str) -> Option<f32> {
+	let number: String = s
+		.chars()
+		.take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+		.map(|c| if c == ',' { '.' } else { c })
+		.collect();
+	number.trim_end_matches('.').parse().ok()
+}
+
+// Parses chapter paths shaped like `vol<N>/<M>` with optional decimal part, suffixes, extra
+// markers (`vol2/extra1`) and query string; missing parts are left for the caller to fill in
+pub fn parse_chapter_path(path: &str) -> ChapterLabel {
+	let path = path.split(['?', '#']).next().unwrap_or_default();
+	let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+
+	let mut result = ChapterLabel::default();
+	let mut chapter_segment = None;
+	for segment in segments.by_ref() {
+		match segment.strip_prefix("vol") {
+			Some(vol_str) => {
+				result.volume = parse_leading_number(vol_str);
+				chapter_segment = segments.next();
+				break;
+			}
+			None => chapter_segment = Some(segment),
+		}
+	}
+
+	if let Some(chapter_str) = chapter_segment {
+		let number_start = chapter_str
+			.find(|c: char| c.is_ascii_digit())
+			.unwrap_or(chapter_str.len());
+		result.chapter = parse_leading_number(&chapter_str[number_start..]);
+		let chapter_str = chapter_str.to_lowercase();
+		result.is_extra = chapter_str
+			.split(|c: char| !c.is_alphabetic())
+			.any(|word| EXTRA_WORDS.contains(&word) || PATH_EXTRA_WORDS.contains(&word));
+	}
+	result
+}
+
+// Chapter paths are generated by the site while labels are typed by translators, so a path
+// with a chapter number and no extra marker makes a regular chapter whatever the label says
+pub fn is_extra_chapter(path: &ChapterLabel, label: &ChapterLabel) -> bool {
+	if path.chapter.is_some() && !path.is_extra {
+		return false;
+	}
+	path.is_extra || label.is_extra
+}
+
+// Extras have numbers of their own, which collide with the regular chapters, so they are
+// numbered after the closest older regular chapter instead: in hundredths by their position
+// among the extras following it. The second extra after chapter 12 becomes 12.02 and extras
+// before any chapter start from 0.01. `chapters` are (number, is extra) pairs from the newest
+// chapter to the oldest one, as the sites list them
+pub fn number_extras(chapters: &[(f32, bool)]) -> Vec<f32> {
+	let mut numbers = Vec::with_capacity(chapters.len());
+	let mut previous_chapter = 0.0;
+	let mut position = 0;
+	for (number, is_extra) in chapters.iter().rev() {
+		if *is_extra {
+			position = (position + 1).min(99);
+			numbers.push(previous_chapter + position as f32 / 100.0);
+		} else {
+			previous_chapter = *number;
+			position = 0;
+			numbers.push(*number);
+		}
+	}
+	numbers.reverse();
+	numbers
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn label(
+		volume: Option<f32>,
+		chapter: Option<f32>,
+		is_extra: bool,
+		title: &str,
+	) -> ChapterLabel {
+		ChapterLabel {
+			volume,
+			chapter,
+			is_extra,
+			title: title.to_string(),
+		}
+	}
+
+	#[test]
+	fn parses_labeled_numbers() {
+		assert_eq!(
+			parse_chapter_label("Том 3 Глава 25 - Начало"),
+			label(Some(3.0), Some(25.0), false, "Начало")
+		);
+		assert_eq!(
+			parse_chapter_label("Том3 Гл.7,5"),
+			label(Some(3.0), Some(7.5), false, "")
+		);
+		assert_eq!(
+			parse_chapter_label("Vol. 2 Chapter 10-11"),
+			label(Some(2.0), Some(10.0), false, "")
+		);
+	}
+
+	#[test]
+	fn parses_volume_headers() {
+		// Madara groups chapters under these
+		assert_eq!(
+			parse_chapter_label("Том 2"),
+			label(Some(2.0), None, false, "")
+		);
+		assert_eq!(
+			parse_chapter_label("Том 1 Глава 100 (часть 2)"),
+			label(Some(1.0), Some(100.02), false, "")
+		);
+	}
+
+	#[test]
+	fn parses_leading_numbers() {
+		assert_eq!(
+			parse_chapter_label("2 - 15 Начало пути"),
+			label(Some(2.0), Some(15.0), false, "Начало пути")
+		);
+		assert_eq!(
+			parse_chapter_label("15 Начало пути"),
+			label(None, Some(15.0), false, "Начало пути")
+		);
+		assert_eq!(
+			parse_chapter_label("Начало пути 15"),
+			label(None, None, false, "Начало пути 15")
+		);
+	}
+
+	#[test]
+	fn parses_parts() {
+		assert_eq!(
+			parse_chapter_label("Глава 100 (часть 2)").chapter,
+			Some(100.02)
+		);
+		assert_eq!(parse_chapter_label("Глава 12 часть 9").chapter, Some(12.09));
+		// part 10 would be 10.1, the same as "Глава 10.1"
+		assert_eq!(
+			parse_chapter_label("Глава 10 (часть 10)").chapter,
+			Some(10.0)
+		);
+		assert_ne!(
+			parse_chapter_label("Глава 10 (часть 10)").chapter,
+			parse_chapter_label("Глава 10.1").chapter
+		);
+		assert_eq!(parse_chapter_label("Часть 4").chapter, Some(4.0));
+		// a decimal chapter keeps its number
+		assert_eq!(
+			parse_chapter_label("Глава 12.5 часть 2").chapter,
+			Some(12.5)
+		);
+	}
+
+	#[test]
+	fn parses_extras() {
+		assert_eq!(
+			parse_chapter_label("Экстра 3"),
+			label(None, Some(3.0), true, "Экстра 3")
+		);
+		assert_eq!(
+			parse_chapter_label("Экстра 2"),
+			label(None, Some(2.0), true, "Экстра 2")
+		);
+		assert_eq!(
+			parse_chapter_label("Том 2 Экстра"),
+			label(Some(2.0), None, true, "Экстра")
+		);
+		assert_eq!(
+			parse_chapter_label("Глава 10 Бонус"),
+			label(None, Some(10.0), true, "Бонус")
+		);
+		assert_eq!(
+			parse_chapter_label("Том 2 Экстра 3"),
+			label(Some(2.0), Some(3.0), true, "Экстра 3")
+		);
+		assert_eq!(
+			parse_chapter_label("Пролог"),
+			label(None, Some(0.0), false, "Пролог")
+		);
+	}
+
+	#[test]
+	fn keeps_extra_words_of_titles() {
+		assert_eq!(
+			parse_chapter_label("Глава 12 - My Ex"),
+			label(None, Some(12.0), false, "My Ex")
+		);
+		assert_eq!(
+			parse_chapter_label("Глава 12 - Бонус за храбрость"),
+			label(None, Some(12.0), false, "Бонус за храбрость")
+		);
+		assert_eq!(
+			parse_chapter_label("3 - 25 Бонус за храбрость"),
+			label(Some(3.0), Some(25.0), false, "Бонус за храбрость")
+		);
+	}
+
+	#[test]
+	fn parses_paths() {
+		assert_eq!(
+			parse_chapter_path("vol1/5"),
+			label(Some(1.0), Some(5.0), false, "")
+		);
+		assert_eq!(
+			parse_chapter_path("vol3/12.5?tran=7"),
+			label(Some(3.0), Some(12.5), false, "")
+		);
+		assert_eq!(
+			parse_chapter_path("vol2/extra1"),
+			label(Some(2.0), Some(1.0), true, "")
+		);
+		assert_eq!(
+			parse_chapter_path("vol0/ex2#page=3"),
+			label(Some(0.0), Some(2.0), true, "")
+		);
+		assert_eq!(parse_chapter_path("7"), label(None, Some(7.0), false, ""));
+	}
+
+	#[test]
+	fn lets_numbered_paths_decide_extras() {
+		let path = parse_chapter_path("vol3/25");
+		let label = parse_chapter_label("3 - 25 Бонус за храбрость");
+		assert!(!is_extra_chapter(&path, &label));
+		assert_eq!(path.chapter, Some(25.0));
+		// an extra would have been renumbered to 24.01 after the chapter before it
+		let row = (path.chapter.unwrap(), is_extra_chapter(&path, &label));
+		assert_eq!(
+			number_extras(&[(26.0, false), row, (24.0, false)]),
+			vec![26.0, 25.0, 24.0]
+		);
+
+		let extra_path = parse_chapter_path("vol3/extra1");
+		assert!(is_extra_chapter(
+			&extra_path,
+			&parse_chapter_label("Экстра")
+		));
+		// without a number in the path the label decides
+		assert!(is_extra_chapter(
+			&parse_chapter_path("vol3/special"),
+			&parse_chapter_label("Бонус")
+		));
+	}
+
+	#[test]
+	fn numbers_extras_after_regular_chapters() {
+		// newest first: vol2/extra1 follows chapter 12 and must not collide with chapter 1
+		let chapters = [
+			(13.0, false),
+			(2.0, true),
+			(1.0, true),
+			(12.0, false),
+			(1.0, false),
+			(1.0, true),
+		];
+		assert_eq!(
+			number_extras(&chapters),
+			vec![13.0, 12.02, 12.01, 12.0, 1.0, 0.01]
+		);
+	}
+}
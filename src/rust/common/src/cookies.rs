@@ -0,0 +1,72 @@
+use alloc::{string::String, vec::Vec};
+
+// Keeps only `name=value` pairs of `Set-Cookie` headers, attributes like path or expiration date
+// are dropped. Several headers come joined with commas, and so do the dates of `Expires`, which
+// is why parts without `=` are skipped too. Cookies with empty values are deleted ones.
+pub fn extract_cookie_pairs(set_cookie: &str) -> String {
+	const ATTRIBUTES: [&str; 8] = [
+		"path", "domain", "expires", "max-age", "samesite", "secure", "httponly", "priority",
+	];
+
+	let pairs: Vec<_> = set_cookie
+		.split([';', ','])
+		.map(str::trim)
+		.filter(|part| {
+			part.split_once('=').is_some_and(|(name, value)| {
+				!name.is_empty()
+					&& !value.is_empty()
+					&& !ATTRIBUTES.contains(&name.to_lowercase().as_str())
+			})
+		})
+		.collect();
+	pairs.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn keeps_single_pair() {
+		assert_eq!(
+			extract_cookie_pairs("JSESSIONID=abc123"),
+			"JSESSIONID=abc123"
+		);
+	}
+
+	#[test]
+	fn drops_attributes() {
+		assert_eq!(
+			extract_cookie_pairs(
+				"JSESSIONID=abc123; Path=/; Domain=.readmanga.live; HttpOnly; Secure; SameSite=Lax; Priority=High"
+			),
+			"JSESSIONID=abc123"
+		);
+	}
+
+	#[test]
+	fn splits_joined_headers() {
+		assert_eq!(
+			extract_cookie_pairs(
+				"JSESSIONID=abc123; Path=/; HttpOnly, remember_me=dXNlcjox==; Expires=Wed, 21 Oct 2026 07:28:00 GMT; Max-Age=2592000; path=/, mtr=true"
+			),
+			"JSESSIONID=abc123; remember_me=dXNlcjox==; mtr=true"
+		);
+	}
+
+	#[test]
+	fn skips_deleted_cookies() {
+		assert_eq!(
+			extract_cookie_pairs(
+				"remember_me=; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT, JSESSIONID=abc123"
+			),
+			"JSESSIONID=abc123"
+		);
+	}
+
+	#[test]
+	fn handles_missing_cookies() {
+		assert_eq!(extract_cookie_pairs(""), "");
+		assert_eq!(extract_cookie_pairs("Path=/; HttpOnly"), "");
+	}
+}
@@ -0,0 +1,407 @@
+// Minimal implementations of what CryptoJS uses for `AES.decrypt(data, password)`: MD5 based
+// EVP_BytesToKey, AES-256-CBC with PKCS#7 padding and base64
+use alloc::vec::Vec;
+
+const MD5_SHIFTS: [u32; 64] = [
+	7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+	14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+	21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_CONSTANTS: [u32; 64] = [
+	0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+	0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+	0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+	0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+	0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+	0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+	0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+	0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+pub fn md5(data: &[u8]) -> [u8; 16] {
+	let mut message = data.to_vec();
+	message.push(0x80);
+	while message.len() % 64 != 56 {
+		message.push(0);
+	}
+	message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+	let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+	for chunk in message.chunks(64) {
+		let words: Vec<u32> = chunk
+			.chunks(4)
+			.map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+			.collect();
+
+		let [mut a, mut b, mut c, mut d] = state;
+		for i in 0..64 {
+			let (f, g) = match i / 16 {
+				0 => ((b & c) | (!b & d), i),
+				1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+				2 => (b ^ c ^ d, (3 * i + 5) % 16),
+				_ => (c ^ (b | !d), (7 * i) % 16),
+			};
+			let rotated = a
+				.wrapping_add(f)
+				.wrapping_add(MD5_CONSTANTS[i])
+				.wrapping_add(words[g])
+				.rotate_left(MD5_SHIFTS[i]);
+			a = d;
+			d = c;
+			c = b;
+			b = b.wrapping_add(rotated);
+		}
+
+		for (value, added) in state.iter_mut().zip([a, b, c, d]) {
+			*value = value.wrapping_add(added);
+		}
+	}
+
+	let mut digest = [0u8; 16];
+	for (bytes, value) in digest.chunks_mut(4).zip(state) {
+		bytes.copy_from_slice(&value.to_le_bytes());
+	}
+	digest
+}
+
+// OpenSSL's EVP_BytesToKey with MD5 and a single iteration, as used by CryptoJS for passphrases
+pub fn evp_bytes_to_key(
+	password: &[u8],
+	salt: &[u8],
+	key_len: usize,
+	iv_len: usize,
+) -> (Vec<u8>, Vec<u8>) {
+	let mut derived: Vec<u8> = Vec::new();
+	let mut block: Vec<u8> = Vec::new();
+	while derived.len() < key_len + iv_len {
+		let mut input = block.clone();
+		input.extend_from_slice(password);
+		input.extend_from_slice(salt);
+		block = md5(&input).to_vec();
+		derived.extend_from_slice(&block);
+	}
+	let iv = derived[key_len..key_len + iv_len].to_vec();
+	derived.truncate(key_len);
+	(derived, iv)
+}
+
+const fn gf_multiply(mut a: u8, mut b: u8) -> u8 {
+	let mut result = 0;
+	while b != 0 {
+		if b & 1 != 0 {
+			result ^= a;
+		}
+		let carry = a & 0x80 != 0;
+		a <<= 1;
+		if carry {
+			a ^= 0x1b;
+		}
+		b >>= 1;
+	}
+	result
+}
+
+const fn generate_sboxes() -> ([u8; 256], [u8; 256]) {
+	let mut sbox = [0u8; 256];
+	let mut inverse_sbox = [0u8; 256];
+	let mut i = 0;
+	while i < 256 {
+		// multiplicative inverse in GF(2^8), 0 maps to itself
+		let mut inverse = 0u8;
+		if i != 0 {
+			let mut candidate = 1;
+			while candidate < 256 {
+				if gf_multiply(i as u8, candidate as u8) == 1 {
+					inverse = candidate as u8;
+					break;
+				}
+				candidate += 1;
+			}
+		}
+		let value = inverse
+			^ inverse.rotate_left(1)
+			^ inverse.rotate_left(2)
+			^ inverse.rotate_left(3)
+			^ inverse.rotate_left(4)
+			^ 0x63;
+		sbox[i] = value;
+		inverse_sbox[value as usize] = i as u8;
+		i += 1;
+	}
+	(sbox, inverse_sbox)
+}
+
+const SBOXES: ([u8; 256], [u8; 256]) = generate_sboxes();
+const SBOX: [u8; 256] = SBOXES.0;
+const INVERSE_SBOX: [u8; 256] = SBOXES.1;
+
+const AES_256_ROUNDS: usize = 14;
+
+fn expand_key(key: &[u8]) -> [[u8; 16]; AES_256_ROUNDS + 1] {
+	const KEY_WORDS: usize = 8;
+	let mut words = [[0u8; 4]; 4 * (AES_256_ROUNDS + 1)];
+	for (word, bytes) in words.iter_mut().zip(key.chunks(4)) {
+		word.copy_from_slice(bytes);
+	}
+
+	let mut round_constant = 1u8;
+	for i in KEY_WORDS..words.len() {
+		let mut temp = words[i - 1];
+		if i % KEY_WORDS == 0 {
+			temp = [
+				SBOX[temp[1] as usize] ^ round_constant,
+				SBOX[temp[2] as usize],
+				SBOX[temp[3] as usize],
+				SBOX[temp[0] as usize],
+			];
+			round_constant = gf_multiply(round_constant, 2);
+		} else if i % KEY_WORDS == 4 {
+			temp = temp.map(|byte| SBOX[byte as usize]);
+		}
+		for j in 0..4 {
+			words[i][j] = words[i - KEY_WORDS][j] ^ temp[j];
+		}
+	}
+
+	let mut round_keys = [[0u8; 16]; AES_256_ROUNDS + 1];
+	for (round_key, round_words) in round_keys.iter_mut().zip(words.chunks(4)) {
+		for (bytes, word) in round_key.chunks_mut(4).zip(round_words) {
+			bytes.copy_from_slice(word);
+		}
+	}
+	round_keys
+}
+
+fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
+	for (byte, key_byte) in state.iter_mut().zip(round_key) {
+		*byte ^= key_byte;
+	}
+}
+
+// state is stored column by column, so byte `row + 4 * column`
+fn inverse_shift_rows(state: &mut [u8; 16]) {
+	let original = *state;
+	for row in 1..4 {
+		for column in 0..4 {
+			state[row + 4 * ((column + row) % 4)] = original[row + 4 * column];
+		}
+	}
+}
+
+fn inverse_mix_columns(state: &mut [u8; 16]) {
+	for column in state.chunks_mut(4) {
+		let [a, b, c, d] = [column[0], column[1], column[2], column[3]];
+		column[0] =
+			gf_multiply(a, 14) ^ gf_multiply(b, 11) ^ gf_multiply(c, 13) ^ gf_multiply(d, 9);
+		column[1] =
+			gf_multiply(a, 9) ^ gf_multiply(b, 14) ^ gf_multiply(c, 11) ^ gf_multiply(d, 13);
+		column[2] =
+			gf_multiply(a, 13) ^ gf_multiply(b, 9) ^ gf_multiply(c, 14) ^ gf_multiply(d, 11);
+		column[3] =
+			gf_multiply(a, 11) ^ gf_multiply(b, 13) ^ gf_multiply(c, 9) ^ gf_multiply(d, 14);
+	}
+}
+
+fn decrypt_block(block: &mut [u8; 16], round_keys: &[[u8; 16]; AES_256_ROUNDS + 1]) {
+	add_round_key(block, &round_keys[AES_256_ROUNDS]);
+	for round in (0..AES_256_ROUNDS).rev() {
+		inverse_shift_rows(block);
+		for byte in block.iter_mut() {
+			*byte = INVERSE_SBOX[*byte as usize];
+		}
+		add_round_key(block, &round_keys[round]);
+		if round > 0 {
+			inverse_mix_columns(block);
+		}
+	}
+}
+
+pub fn aes_256_cbc_decrypt(data: &[u8], key: &[u8], iv: &[u8]) -> Option<Vec<u8>> {
+	if key.len() != 32 || iv.len() != 16 || data.is_empty() || !data.len().is_multiple_of(16) {
+		return None;
+	}
+	let round_keys = expand_key(key);
+
+	let mut result = Vec::with_capacity(data.len());
+	let mut previous = [0u8; 16];
+	previous.copy_from_slice(iv);
+	for chunk in data.chunks(16) {
+		let mut block = [0u8; 16];
+		block.copy_from_slice(chunk);
+		decrypt_block(&mut block, &round_keys);
+		for (byte, previous_byte) in block.iter_mut().zip(previous) {
+			*byte ^= previous_byte;
+		}
+		result.extend_from_slice(&block);
+		previous.copy_from_slice(chunk);
+	}
+
+	// PKCS#7 padding, a wrong one means a wrong password
+	let padding = *result.last()? as usize;
+	if padding == 0
+		|| padding > 16
+		|| result[result.len() - padding..]
+			.iter()
+			.any(|byte| *byte as usize != padding)
+	{
+		return None;
+	}
+	result.truncate(result.len() - padding);
+	Some(result)
+}
+
+// Standard and URL-safe alphabets, padding is optional but has to be correct when present
+pub fn base64_decode(data: &str) -> Option<Vec<u8>> {
+	let data: Vec<u8> = data.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+	let padding = data.iter().rev().take_while(|c| **c == b'=').count();
+	let symbols = &data[..data.len() - padding];
+	if padding > 2
+		|| symbols.len() % 4 == 1
+		|| (padding > 0 && !data.len().is_multiple_of(4))
+		|| symbols.contains(&b'=')
+	{
+		return None;
+	}
+
+	let mut result = Vec::with_capacity(symbols.len() / 4 * 3);
+	let mut buffer = 0u32;
+	let mut bits = 0;
+	for c in symbols {
+		let value = match c {
+			b'A'..=b'Z' => c - b'A',
+			b'a'..=b'z' => c - b'a' + 26,
+			b'0'..=b'9' => c - b'0' + 52,
+			b'+' | b'-' => 62,
+			b'/' | b'_' => 63,
+			_ => return None,
+		};
+		buffer = (buffer << 6) | value as u32;
+		bits += 6;
+		if bits >= 8 {
+			bits -= 8;
+			result.push((buffer >> bits) as u8);
+		}
+	}
+	Some(result)
+}
+
+pub fn hex_decode(data: &str) -> Option<Vec<u8>> {
+	if !data.len().is_multiple_of(2) {
+		return None;
+	}
+	(0..data.len())
+		.step_by(2)
+		.map(|idx| u8::from_str_radix(data.get(idx..idx + 2)?, 16).ok())
+		.collect()
+}
+
+// `AES.decrypt` with a passphrase: key and iv are both derived from the password and the salt
+pub fn decrypt(ciphertext: &[u8], salt: &[u8], password: &str) -> Option<Vec<u8>> {
+	let (key, iv) = evp_bytes_to_key(password.as_bytes(), salt, 32, 16);
+	aes_256_cbc_decrypt(ciphertext, &key, &iv)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn hex(bytes: &[u8]) -> String {
+		bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+	}
+
+	#[test]
+	fn md5_matches_rfc_1321() {
+		let vectors = [
+			("", "d41d8cd98f00b204e9800998ecf8427e"),
+			("a", "0cc175b9c0f1b6a831c399e269772661"),
+			("abc", "900150983cd24fb0d6963f7d28e17f72"),
+			("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+			(
+				"abcdefghijklmnopqrstuvwxyz",
+				"c3fcd3d76192e4007dfb496cca67e13b",
+			),
+			(
+				"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+				"d174ab98d277d9f5a5611c2c9f419d9f",
+			),
+			(
+				"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+				"57edf4a22be3c955ac49da2e2107b67a",
+			),
+		];
+		for (input, digest) in vectors {
+			assert_eq!(hex(&md5(input.as_bytes())), digest, "{input:?}");
+		}
+	}
+
+	// printf 'The quick brown fox jumps over the lazy dog' | \
+	//   openssl enc -aes-256-cbc -md md5 -pass pass:password -S 0102030405060708 -base64 -A
+	const OPENSSL_CIPHERTEXT: &str =
+		"941Tre0OF9/nO0AfAMYSO05Gm6LpYuqrI65UUBKCHt6Agmr/t1u9JRWvoSLgozmy";
+	const OPENSSL_SALT: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+	#[test]
+	fn decrypts_openssl_output() {
+		let ciphertext = base64_decode(OPENSSL_CIPHERTEXT).unwrap();
+		assert_eq!(
+			decrypt(&ciphertext, &OPENSSL_SALT, "password").as_deref(),
+			Some(b"The quick brown fox jumps over the lazy dog".as_slice())
+		);
+	}
+
+	#[test]
+	fn derives_openssl_key_and_iv() {
+		// `openssl enc -p` with the password and the salt below
+		let salt = hex_decode("5d8e2a7c91b4f036").unwrap();
+		let (key, iv) = evp_bytes_to_key(b"9c2f0a1b3d", &salt, 32, 16);
+		assert_eq!(
+			hex(&key),
+			"61698c6441eecccdfbe8b0a77f2052e9707339432ea54cb1a2d716ca37257bcb"
+		);
+		assert_eq!(hex(&iv), "a379bd9cb05524cd0b7e8305dccf43fd");
+	}
+
+	#[test]
+	fn rejects_wrong_password() {
+		let ciphertext = base64_decode(OPENSSL_CIPHERTEXT).unwrap();
+		assert_eq!(decrypt(&ciphertext, &OPENSSL_SALT, "passw0rd"), None);
+	}
+
+	#[test]
+	fn rejects_truncated_ciphertext() {
+		let ciphertext = base64_decode(OPENSSL_CIPHERTEXT).unwrap();
+		assert_eq!(decrypt(&ciphertext[..40], &OPENSSL_SALT, "password"), None);
+		assert_eq!(decrypt(&[], &OPENSSL_SALT, "password"), None);
+	}
+
+	#[test]
+	fn decodes_base64() {
+		assert_eq!(base64_decode("TWFu").as_deref(), Some(b"Man".as_slice()));
+		assert_eq!(base64_decode("TWE=").as_deref(), Some(b"Ma".as_slice()));
+		assert_eq!(base64_decode("TQ==").as_deref(), Some(b"M".as_slice()));
+		assert_eq!(base64_decode("TQ").as_deref(), Some(b"M".as_slice()));
+		assert_eq!(base64_decode("TW\nFu").as_deref(), Some(b"Man".as_slice()));
+		assert_eq!(
+			base64_decode("-_8=").as_deref(),
+			Some([0xfb, 0xff].as_slice())
+		);
+	}
+
+	#[test]
+	fn rejects_bad_base64() {
+		for data in ["TW*u", "TQ=", "TQ===", "T===", "TQ==TQ==", "T=Q=", "TWFuT"] {
+			assert_eq!(base64_decode(data), None, "{data}");
+		}
+	}
+
+	#[test]
+	fn decodes_hex() {
+		assert_eq!(
+			hex_decode("00ff7a").as_deref(),
+			Some([0, 0xff, 0x7a].as_slice())
+		);
+		assert_eq!(hex_decode("abc"), None);
+		assert_eq!(hex_decode("zz"), None);
+	}
+}
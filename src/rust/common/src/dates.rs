@@ -0,0 +1,320 @@
+use alloc::vec::Vec;
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+// Prefixes of Russian and English month names, which cover genitive ("марта"), nominative
+// ("март") and short ("мар.", "Mar") forms
+const MONTH_PREFIXES: [&[&str]; 12] = [
+	&["янв", "jan"],
+	&["фев", "feb"],
+	&["мар", "mar"],
+	&["апр", "apr"],
+	&["мая", "май", "may"],
+	&["июн", "jun"],
+	&["июл", "jul"],
+	&["авг", "aug"],
+	&["сен", "sep"],
+	&["окт", "oct"],
+	&["ноя", "nov"],
+	&["дек", "dec"],
+];
+
+// Both Russian and English units are accepted, since the sites mix them up
+fn unit_seconds(unit: &str) -> Option<f64> {
+	const UNITS: [(&str, f64); 15] = [
+		("сек", 1.0),
+		("sec", 1.0),
+		("мин", MINUTE),
+		("min", MINUTE),
+		("час", HOUR),
+		("hour", HOUR),
+		// "день", "дня", "дней"
+		("ден", DAY),
+		("дн", DAY),
+		("day", DAY),
+		("недел", 7.0 * DAY),
+		("week", 7.0 * DAY),
+		("месяц", 30.0 * DAY),
+		("month", 30.0 * DAY),
+		("год", 365.0 * DAY),
+		("year", 365.0 * DAY),
+	];
+	if unit == "лет" {
+		return Some(365.0 * DAY);
+	}
+	UNITS
+		.iter()
+		.find(|(prefix, _)| unit.starts_with(prefix))
+		.map(|(_, seconds)| *seconds)
+}
+
+// "вчера", "2 дня назад", "неделю назад", "3 days ago", "an hour ago"; `now` is a timestamp in
+// seconds
+pub fn parse_relative_date(text: &str, now: f64) -> Option<f64> {
+	let text = text.trim().to_lowercase();
+	let days_ago = match text.as_str() {
+		"сегодня" | "today" => Some(0.0),
+		"вчера" | "yesterday" => Some(1.0),
+		"позавчера" => Some(2.0),
+		_ => None,
+	};
+	if let Some(days_ago) = days_ago {
+		return Some(now - days_ago * DAY);
+	}
+
+	let words: Vec<_> = text
+		.strip_suffix("назад")
+		.or_else(|| text.strip_suffix("ago"))?
+		.split_whitespace()
+		.collect();
+	let (count, unit) = match words[..] {
+		["a" | "an", unit] => (1.0, unit),
+		[count, unit] => (count.parse().ok()?, unit),
+		[unit] => (1.0, unit),
+		_ => return None,
+	};
+	Some(now - count * unit_seconds(unit)?)
+}
+
+fn parse_month(word: &str) -> Option<u32> {
+	let word = word.trim_end_matches(['.', ',']).to_lowercase();
+	MONTH_PREFIXES
+		.iter()
+		.position(|prefixes| prefixes.iter().any(|prefix| word.starts_with(prefix)))
+		.map(|idx| idx as u32 + 1)
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let month = month as i64;
+	let day_of_year =
+		(153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146097 + day_of_era - 719468
+}
+
+fn year_of(timestamp: f64) -> i64 {
+	let days = (timestamp / DAY) as i64;
+	// the year which starts closest before the day
+	let mut year = 1970 + days.div_euclid(365);
+	while days_from_civil(year, 1, 1) > days {
+		year -= 1;
+	}
+	year
+}
+
+// Dates written with numbers only, like "15.03.24" for "dd.MM.yy" or
+// "2024-03-15 18:30:00.000" for "yyyy-MM-dd HH:mm:ss.SSS"; the time is taken as UTC.
+// Two-digit years are of the current century
+pub fn parse_numeric_date(text: &str, format: &str) -> Option<f64> {
+	let mut text = text.trim();
+	let mut fields = [1970, 1, 1, 0, 0, 0];
+	let mut format_chars = format.chars().peekable();
+	while let Some(token) = format_chars.next() {
+		let mut width = 1;
+		while format_chars.next_if_eq(&token).is_some() {
+			width += 1;
+		}
+		let field = match token {
+			'y' => 0,
+			'M' => 1,
+			'd' => 2,
+			'H' => 3,
+			'm' => 4,
+			's' => 5,
+			'S' => 6,
+			literal => {
+				text = text.strip_prefix(literal)?;
+				continue;
+			}
+		};
+		let digits = text
+			.get(..width)
+			.filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))?;
+		text = &text[width..];
+		let value: i64 = digits.parse().ok()?;
+		match (field, width) {
+			// fractions of a second are dropped
+			(6, _) => {}
+			(0, 2) => fields[0] = 2000 + value,
+			_ => fields[field] = value,
+		}
+	}
+
+	let [year, month, day, hour, minute, second] = fields;
+	if !text.is_empty() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+		return None;
+	}
+	let days = days_from_civil(year, month as u32, day as u32);
+	Some(days as f64 * DAY + (hour * 3600 + minute * 60 + second) as f64)
+}
+
+// "15 марта 2024", "15 марта 2024 г.", "March 15, 2024" or "15 марта" of the last year, in
+// which the date has already come; midnight UTC of the date is returned
+pub fn parse_month_name_date(text: &str, now: f64) -> Option<f64> {
+	let words: Vec<_> = text
+		.split_whitespace()
+		.map(|word| word.trim_end_matches(','))
+		.filter(|word| !matches!(*word, "г." | "г" | "года"))
+		.collect();
+	let (day, month, year) = match words[..] {
+		[day, month, ..] if day.starts_with(|c: char| c.is_ascii_digit()) => {
+			(day, month, words.get(2).copied())
+		}
+		[month, day, ..] => (day, month, words.get(2).copied()),
+		_ => return None,
+	};
+	let day: u32 = day.parse().ok()?;
+	let month = parse_month(month)?;
+	if !(1..=31).contains(&day) {
+		return None;
+	}
+	let date = match year {
+		Some(year) => days_from_civil(year.trim_end_matches("г.").parse().ok()?, month, day),
+		None => {
+			// "20 декабря" read in January is from the previous year
+			let date = days_from_civil(year_of(now), month, day);
+			if date as f64 * DAY > now {
+				days_from_civil(year_of(now) - 1, month, day)
+			} else {
+				date
+			}
+		}
+	};
+	Some(date as f64 * DAY)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// 2024-03-15 12:00:00 UTC
+	const NOW: f64 = 1_710_504_000.0;
+
+	#[test]
+	fn parses_russian_relative_dates() {
+		assert_eq!(parse_relative_date("сегодня", NOW), Some(NOW));
+		assert_eq!(parse_relative_date("Вчера", NOW), Some(NOW - DAY));
+		assert_eq!(parse_relative_date("позавчера", NOW), Some(NOW - 2.0 * DAY));
+		assert_eq!(
+			parse_relative_date("3 дня назад", NOW),
+			Some(NOW - 3.0 * DAY)
+		);
+		assert_eq!(parse_relative_date("час назад", NOW), Some(NOW - HOUR));
+		assert_eq!(parse_relative_date("день назад", NOW), Some(NOW - DAY));
+		assert_eq!(
+			parse_relative_date("неделю назад", NOW),
+			Some(NOW - 7.0 * DAY)
+		);
+		assert_eq!(
+			parse_relative_date("5 лет назад", NOW),
+			Some(NOW - 5.0 * 365.0 * DAY)
+		);
+		assert_eq!(
+			parse_relative_date("15 минут назад", NOW),
+			Some(NOW - 15.0 * MINUTE)
+		);
+	}
+
+	#[test]
+	fn parses_english_relative_dates() {
+		assert_eq!(parse_relative_date("yesterday", NOW), Some(NOW - DAY));
+		assert_eq!(
+			parse_relative_date("3 days ago", NOW),
+			Some(NOW - 3.0 * DAY)
+		);
+		assert_eq!(parse_relative_date("an hour ago", NOW), Some(NOW - HOUR));
+		assert_eq!(
+			parse_relative_date("2 weeks ago", NOW),
+			Some(NOW - 14.0 * DAY)
+		);
+		assert_eq!(
+			parse_relative_date("10 mins ago", NOW),
+			Some(NOW - 10.0 * MINUTE)
+		);
+	}
+
+	#[test]
+	fn rejects_unknown_relative_dates() {
+		assert_eq!(parse_relative_date("15.03.2024", NOW), None);
+		assert_eq!(parse_relative_date("давно назад", NOW), None);
+		assert_eq!(parse_relative_date("", NOW), None);
+	}
+
+	#[test]
+	fn parses_month_name_dates() {
+		const MARCH_15: f64 = 1_710_460_800.0;
+		assert_eq!(parse_month_name_date("15 марта 2024", NOW), Some(MARCH_15));
+		assert_eq!(
+			parse_month_name_date("15 марта 2024 г.", NOW),
+			Some(MARCH_15)
+		);
+		assert_eq!(parse_month_name_date("15 мар. 2024", NOW), Some(MARCH_15));
+		assert_eq!(parse_month_name_date("March 15, 2024", NOW), Some(MARCH_15));
+		assert_eq!(parse_month_name_date("15 March 2024", NOW), Some(MARCH_15));
+		assert_eq!(parse_month_name_date("15 марта", NOW), Some(MARCH_15));
+		assert_eq!(
+			parse_month_name_date("1 мая 2023", NOW),
+			Some(1_682_899_200.0)
+		);
+		assert_eq!(
+			parse_month_name_date("29 февраля 2000", NOW),
+			Some(951_782_400.0)
+		);
+		assert_eq!(
+			parse_month_name_date("31 декабря 1999", NOW),
+			Some(946_598_400.0)
+		);
+	}
+
+	#[test]
+	fn takes_last_year_for_future_year_less_dates() {
+		assert_eq!(
+			parse_month_name_date("20 декабря", NOW),
+			parse_month_name_date("20 декабря 2023", NOW)
+		);
+		// today is not in the future yet
+		assert_eq!(
+			parse_month_name_date("15 марта", NOW + 11.0 * HOUR),
+			Some(1_710_460_800.0)
+		);
+	}
+
+	#[test]
+	fn parses_numeric_dates() {
+		const MARCH_15: f64 = 1_710_460_800.0;
+		assert_eq!(parse_numeric_date("15.03.24", "dd.MM.yy"), Some(MARCH_15));
+		assert_eq!(
+			parse_numeric_date("15.03.2024", "dd.MM.yyyy"),
+			Some(MARCH_15)
+		);
+		assert_eq!(
+			parse_numeric_date(" 15-03-2024 ", "dd-MM-yyyy"),
+			Some(MARCH_15)
+		);
+		assert_eq!(
+			parse_numeric_date("2024-03-15 18:30:05.120", "yyyy-MM-dd HH:mm:ss.SSS"),
+			Some(MARCH_15 + 18.0 * HOUR + 30.0 * MINUTE + 5.0)
+		);
+	}
+
+	#[test]
+	fn rejects_other_numeric_dates() {
+		assert_eq!(parse_numeric_date("15.03.2024", "dd.MM.yy"), None);
+		assert_eq!(parse_numeric_date("15.13.24", "dd.MM.yy"), None);
+		assert_eq!(parse_numeric_date("15 марта", "dd.MM.yy"), None);
+		assert_eq!(parse_numeric_date("", "dd.MM.yyyy"), None);
+	}
+
+	#[test]
+	fn rejects_unknown_month_name_dates() {
+		assert_eq!(parse_month_name_date("15 чего-то 2024", NOW), None);
+		assert_eq!(parse_month_name_date("3 дня назад", NOW), None);
+		assert_eq!(parse_month_name_date("40 марта 2024", NOW), None);
+	}
+}
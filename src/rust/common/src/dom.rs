@@ -0,0 +1,13 @@
+use alloc::{string::String, vec::Vec};
+
+// Part of the DOM API the shared parsers need. Sources implement it for their node wrappers and
+// the tests for a host HTML parser, so the same parsing code runs against recorded markup.
+pub trait Element: Sized {
+	// Matching elements, the element itself included
+	fn select(&self, selector: &str) -> Vec<Self>;
+	fn attr(&self, name: &str) -> Option<String>;
+	// Text content with collapsed whitespace
+	fn text(&self) -> String;
+	// Raw contents of a script element, which `text` leaves out
+	fn data(&self) -> String;
+}
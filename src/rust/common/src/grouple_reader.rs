@@ -0,0 +1,399 @@
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use crate::{
+	dom::Element,
+	js_literal::{self, JsValue},
+};
+
+// Page as it is passed to `rm_h.readerDoInit`: [server, middle, path, width, height, ...]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReaderPage {
+	pub server: String,
+	pub middle: String,
+	pub path: String,
+}
+
+impl ReaderPage {
+	// Server and path of the image, the server is empty for images served by the site itself,
+	// whose path is then relative to the site root
+	pub fn server_and_path(&self) -> (&str, String) {
+		if self.middle.is_empty() && self.path.starts_with("/static/") {
+			("", self.path.clone())
+		} else if self.middle.starts_with("/manga/") {
+			(&self.server, self.path.clone())
+		} else {
+			(&self.server, format!("{}{}", self.middle, self.path))
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReaderData {
+	pub pages: Vec<ReaderPage>,
+	// alternative image servers offered by the reader
+	pub servers: Vec<String>,
+}
+
+pub fn normalize_image_url(url: String) -> String {
+	let url = if !url.contains("://") {
+		format!("https:{url}")
+	} else {
+		url
+	};
+	if url.contains("one-way.work") {
+		match url.split_once('?') {
+			Some((without_query, _)) => without_query.to_string(),
+			None => url,
+		}
+	} else {
+		url
+	}
+}
+
+fn extract_server_url(server: &JsValue) -> Option<String> {
+	// servers are either plain urls or objects like {"path":"https://t1.rmr.rocks/","res":false}
+	let url = server
+		.as_str()
+		.or_else(|| server.get("path")?.as_str())
+		.or_else(|| server.get("url")?.as_str())?;
+	if url.starts_with("http") || url.starts_with("//") {
+		Some(normalize_image_url(url.to_string()))
+	} else {
+		None
+	}
+}
+
+fn extract_image_servers(script: &str, init_args: &[JsValue]) -> Vec<String> {
+	let assigned_servers = js_literal::parse_assignment(script, "servers");
+	assigned_servers
+		.iter()
+		.chain(init_args)
+		.filter_map(JsValue::as_array)
+		.filter(|items| !items.is_empty() && items.iter().all(|s| extract_server_url(s).is_some()))
+		.flat_map(|items| items.iter().filter_map(extract_server_url))
+		.collect()
+}
+
+// Reads pages and image servers from the reader script, fails if the script has no complete
+// `rm_h.readerDoInit(...)` call
+pub fn parse_reader_script(script: &str) -> Option<ReaderData> {
+	let init_args = js_literal::parse_call_arguments(script, "rm_h.readerDoInit")?;
+
+	// the only argument shaped as an array of arrays is the page list
+	let pages_arg = init_args
+		.iter()
+		.filter_map(JsValue::as_array)
+		.find(|items| items.first().and_then(JsValue::as_array).is_some())?;
+
+	let pages = pages_arg
+		.iter()
+		.filter_map(JsValue::as_array)
+		.filter_map(|parts| match parts {
+			[server, middle, path, ..] => {
+				let part = |value: &JsValue| value.to_plain_string().unwrap_or_default();
+				Some(ReaderPage {
+					server: part(server),
+					middle: part(middle),
+					path: part(path),
+				})
+			}
+			_ => None,
+		})
+		.collect();
+
+	Some(ReaderData {
+		pages,
+		servers: extract_image_servers(script, &init_args),
+	})
+}
+
+// The reader controls of a chapter page hold several scripts, one of them initializes the reader
+pub fn parse_reader_page<E: Element>(html: &E) -> Option<ReaderData> {
+	html.select("div.reader-controller > script[type='text/javascript']")
+		.iter()
+		.rev()
+		.find_map(|script_node| parse_reader_script(&script_node.data()))
+}
+
+pub fn get_url_host(url: &str) -> &str {
+	url.split_once("//")
+		.map_or(url, |(_, rest)| rest)
+		.split(['/', '?', '#'])
+		.next()
+		.unwrap_or_default()
+}
+
+// Hosts come from the settings lowercased, a part of the host name is enough
+pub fn matches_host(url: &str, host: &str) -> bool {
+	get_url_host(url).to_lowercase().contains(host)
+}
+
+pub fn matches_any_host(url: &str, hosts: &[String]) -> bool {
+	hosts.iter().any(|host| matches_host(url, host))
+}
+
+// Servers a page can be loaded from: its own one and the reader's alternatives, without
+// excluded hosts and with preferred ones first
+pub fn get_page_servers(
+	own_server: &str,
+	alternative_servers: &[String],
+	preferred_hosts: &[String],
+	excluded_hosts: &[String],
+) -> Vec<String> {
+	let mut servers: Vec<String> = Vec::new();
+	for server in [normalize_image_url(own_server.to_string())]
+		.into_iter()
+		.chain(alternative_servers.iter().cloned())
+	{
+		if !matches_any_host(&server, excluded_hosts) && !servers.contains(&server) {
+			servers.push(server);
+		}
+	}
+	servers.sort_by_key(|server| {
+		preferred_hosts
+			.iter()
+			.position(|host| matches_host(server, host))
+			.unwrap_or(preferred_hosts.len())
+	});
+	servers
+}
+
+// Alternatives of a page travel in the fragment of its url, which is never sent to the server,
+// until the image request hook picks the one to load: `<url>#fallback=<url>,<url>`
+const FALLBACK_MARKER: &str = "#fallback=";
+
+// Commas separate the alternatives, so they are escaped along with `%` and `#`
+fn escape_fallback_url(url: &str) -> String {
+	url.replace('%', "%25")
+		.replace(',', "%2C")
+		.replace('#', "%23")
+}
+
+fn unescape_fallback_url(url: &str) -> String {
+	let mut result = String::with_capacity(url.len());
+	let mut rest = url;
+	while let Some(idx) = rest.find('%') {
+		result.push_str(&rest[..idx]);
+		let escaped = match rest.get(idx..idx + 3) {
+			Some("%25") => "%",
+			Some("%2C") => ",",
+			Some("%23") => "#",
+			_ => "",
+		};
+		if escaped.is_empty() {
+			result.push('%');
+			rest = &rest[idx + 1..];
+		} else {
+			result.push_str(escaped);
+			rest = &rest[idx + 3..];
+		}
+	}
+	result.push_str(rest);
+	result
+}
+
+pub fn with_fallback_urls(url: String, fallback_urls: &[String]) -> String {
+	if fallback_urls.is_empty() {
+		return url;
+	}
+	let fallback_urls: Vec<_> = fallback_urls
+		.iter()
+		.map(|fallback_url| escape_fallback_url(fallback_url))
+		.collect();
+	format!("{url}{FALLBACK_MARKER}{}", fallback_urls.join(","))
+}
+
+// Url to load first and the ones to try when its server is down, in order
+pub fn split_fallback_urls(url: &str) -> (String, Vec<String>) {
+	match url.split_once(FALLBACK_MARKER) {
+		Some((url, fallback_urls)) => (
+			url.to_string(),
+			fallback_urls
+				.split(',')
+				.filter(|fallback_url| !fallback_url.is_empty())
+				.map(unescape_fallback_url)
+				.collect(),
+		),
+		None => (url.to_string(), Vec::new()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	// shape of the reader script on ReadManga chapter pages
+	const READER_SCRIPT: &str = r#"
+		var servers = [{"path":"https://t1.rmr.rocks/","res":false},{"path":"https://t2.rmr.rocks/","res":true}];
+		rm_h.readerInit(1, 'ru', "Глава 1");
+		rm_h.readerDoInit([
+			['https://one-way.work/','','auto/68/83/46/01.jpg_res.jpg?t=1700000000&u=0&h=abc',1000,1500],
+			['https://t1.rmr.rocks/','/manga/some_manga/','vol1/1/02.png',1000,1500],
+			['','','/static/uploads/03.jpg',800,600],
+		], false, null, {"chapterId":123,"name":'It\'s "ok"'}, 0);
+	"#;
+
+	fn page(server: &str, middle: &str, path: &str) -> ReaderPage {
+		ReaderPage {
+			server: server.to_string(),
+			middle: middle.to_string(),
+			path: path.to_string(),
+		}
+	}
+
+	fn hosts(hosts: &[&str]) -> Vec<String> {
+		hosts.iter().map(|host| host.to_string()).collect()
+	}
+
+	#[test]
+	fn reads_pages_and_servers() {
+		let data = parse_reader_script(READER_SCRIPT).unwrap();
+		assert_eq!(
+			data.pages,
+			vec![
+				page(
+					"https://one-way.work/",
+					"",
+					"auto/68/83/46/01.jpg_res.jpg?t=1700000000&u=0&h=abc"
+				),
+				page(
+					"https://t1.rmr.rocks/",
+					"/manga/some_manga/",
+					"vol1/1/02.png"
+				),
+				page("", "", "/static/uploads/03.jpg"),
+			]
+		);
+		assert_eq!(
+			data.servers,
+			hosts(&["https://t1.rmr.rocks/", "https://t2.rmr.rocks/"])
+		);
+	}
+
+	#[test]
+	fn reads_servers_passed_as_argument() {
+		let script = r#"rm_h.readerDoInit([["https://t3.rmr.rocks/","","a/01.jpg",1,1]], false, ["https://t3.rmr.rocks/", "//t4.rmr.rocks/"]);"#;
+		let data = parse_reader_script(script).unwrap();
+		assert_eq!(
+			data.servers,
+			hosts(&["https://t3.rmr.rocks/", "https://t4.rmr.rocks/"])
+		);
+	}
+
+	#[test]
+	fn splits_server_and_path() {
+		let data = parse_reader_script(READER_SCRIPT).unwrap();
+		let sources: Vec<_> = data.pages.iter().map(ReaderPage::server_and_path).collect();
+		assert_eq!(
+			sources,
+			vec![
+				(
+					"https://one-way.work/",
+					"auto/68/83/46/01.jpg_res.jpg?t=1700000000&u=0&h=abc".to_string()
+				),
+				("https://t1.rmr.rocks/", "vol1/1/02.png".to_string()),
+				("", "/static/uploads/03.jpg".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn fails_on_truncated_script() {
+		let call_end = READER_SCRIPT.rfind(");").unwrap();
+		for (idx, _) in READER_SCRIPT[..call_end].char_indices() {
+			assert_eq!(
+				parse_reader_script(&READER_SCRIPT[..idx]),
+				None,
+				"script cut at {idx}"
+			);
+		}
+	}
+
+	#[test]
+	fn fails_without_page_list() {
+		assert_eq!(parse_reader_script("rm_h.readerDoInit(false, null);"), None);
+		assert_eq!(parse_reader_script("var servers = [];"), None);
+	}
+
+	#[test]
+	fn strips_query_from_one_way_urls_only() {
+		assert_eq!(
+			normalize_image_url("https://one-way.work/a.jpg?t=1".to_string()),
+			"https://one-way.work/a.jpg"
+		);
+		assert_eq!(
+			normalize_image_url("//t1.rmr.rocks/a.jpg?t=1".to_string()),
+			"https://t1.rmr.rocks/a.jpg?t=1"
+		);
+	}
+
+	#[test]
+	fn orders_page_servers() {
+		let alternatives = hosts(&["https://t1.rmr.rocks/", "https://t2.rmr.rocks/"]);
+		assert_eq!(
+			get_page_servers("https://one-way.work/", &alternatives, &[], &[]),
+			hosts(&[
+				"https://one-way.work/",
+				"https://t1.rmr.rocks/",
+				"https://t2.rmr.rocks/"
+			])
+		);
+		assert_eq!(
+			get_page_servers(
+				"https://one-way.work/",
+				&alternatives,
+				&hosts(&["t2.rmr"]),
+				&hosts(&["one-way.work"])
+			),
+			hosts(&["https://t2.rmr.rocks/", "https://t1.rmr.rocks/"])
+		);
+		assert!(get_page_servers(
+			"https://t1.rmr.rocks/",
+			&alternatives,
+			&[],
+			&hosts(&["rmr.rocks"])
+		)
+		.is_empty());
+	}
+
+	#[test]
+	fn round_trips_fallback_urls() {
+		let fallback_urls = hosts(&[
+			"https://t1.rmr.rocks/a,b/01.jpg?t=1#x",
+			"https://t2.rmr.rocks/a%2Cb/01.jpg",
+		]);
+		let url = with_fallback_urls("https://one-way.work/01.jpg".to_string(), &fallback_urls);
+		assert_eq!(
+			url,
+			"https://one-way.work/01.jpg#fallback=https://t1.rmr.rocks/a%2Cb/01.jpg?t=1%23x,\
+			 https://t2.rmr.rocks/a%252Cb/01.jpg"
+		);
+		assert_eq!(
+			split_fallback_urls(&url),
+			("https://one-way.work/01.jpg".to_string(), fallback_urls)
+		);
+	}
+
+	#[test]
+	fn keeps_urls_without_fallbacks() {
+		let url = "https://one-way.work/01.jpg".to_string();
+		assert_eq!(with_fallback_urls(url.clone(), &[]), url);
+		assert_eq!(split_fallback_urls(&url), (url, vec![]));
+		assert_eq!(unescape_fallback_url("100%"), "100%");
+	}
+
+	#[test]
+	fn matches_hosts_only() {
+		let excluded = hosts(&["rmr.rocks"]);
+		assert!(matches_any_host("https://T1.RMR.rocks/a.jpg", &excluded));
+		assert!(!matches_any_host(
+			"https://one-way.work/rmr.rocks/a.jpg",
+			&excluded
+		));
+	}
+}
@@ -0,0 +1,32 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::dom::Element;
+
+// Genre and category parameters are named after element ids (`el_2155=in`), which differ between
+// the sites and change over time, unlike the fixed ones like `s_translated`
+const ELEMENT_ID_PREFIX: &str = "el_";
+
+pub fn is_element_id(id: &str) -> bool {
+	id.starts_with(ELEMENT_ID_PREFIX)
+}
+
+// Option label and parameter name of every option of the advanced search form, the input is
+// either referenced by the label or nested into it
+pub fn parse_filter_ids<E: Element>(html: &E) -> Vec<(String, String)> {
+	html.select("label")
+		.iter()
+		.filter_map(|label_node| {
+			let id = label_node
+				.attr("for")
+				.or_else(|| {
+					label_node
+						.select("input")
+						.iter()
+						.find_map(|input_node| input_node.attr("name"))
+				})
+				.filter(|id| is_element_id(id))?;
+			let name = label_node.text().trim().to_lowercase();
+			(!name.is_empty()).then_some((name, id))
+		})
+		.collect()
+}
@@ -0,0 +1,97 @@
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use crate::dom::Element;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileStatus {
+	Ongoing,
+	Completed,
+	Unknown,
+}
+
+// Title card of search results, catalogues and bookmarks (`div.tile`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile {
+	pub id: String,
+	pub cover: String,
+	pub title: String,
+	pub authors: Vec<String>,
+	pub description: String,
+	pub categories: Vec<String>,
+	// categories together with age limitation badges, the content rating is decided from them
+	pub rating_markers: Vec<String>,
+	pub is_adult: bool,
+	pub status: TileStatus,
+}
+
+fn select_last<E: Element>(node: &E, selector: &str) -> Option<E> {
+	node.select(selector).pop()
+}
+
+fn select_texts<E: Element>(node: &E, selector: &str) -> Vec<String> {
+	node.select(selector).iter().map(Element::text).collect()
+}
+
+pub fn parse_tile<E: Element>(node: &E) -> Option<Tile> {
+	let div_img_node = select_last(node, "div.img")?;
+
+	let id = select_last(&div_img_node, "a.non-hover")?
+		.attr("href")?
+		.trim_start_matches('/')
+		.to_string();
+
+	let img_node = select_last(&div_img_node, "img")?;
+	let cover = img_node.attr("data-original")?;
+	let title = img_node.attr("title")?;
+
+	let div_desc_node = select_last(node, "div.desc")?;
+	let div_tile_info_node = select_last(&div_desc_node, "div.tile-info")?;
+	let authors = select_texts(&div_tile_info_node, "a.person-link");
+
+	let div_html_popover_holder_node = select_last(&div_desc_node, "div.html-popover-holder")?;
+	let description = select_last(&div_html_popover_holder_node, "div.manga-description")?.text();
+
+	let mut categories = select_texts(&div_tile_info_node, "a.badge");
+	categories.extend(select_texts(
+		&div_html_popover_holder_node,
+		"span.elem_genre",
+	));
+	categories.extend(select_texts(&div_html_popover_holder_node, "span.elem_tag"));
+
+	let mut rating_markers = categories.clone();
+	rating_markers.extend(select_texts(node, "span.elem_limitation"));
+	let is_adult = !node.select("[class*=mature]").is_empty();
+
+	let has_completed_badge = !node.select("span.mangaTranslationCompleted").is_empty()
+		|| !node.select("span.mangaCompleted").is_empty();
+	let status = if has_completed_badge {
+		TileStatus::Completed
+	} else if div_img_node.select("div.manga-updated").len() == 1 {
+		TileStatus::Ongoing
+	} else {
+		TileStatus::Unknown
+	};
+
+	Some(Tile {
+		id,
+		cover,
+		title,
+		authors,
+		description,
+		categories,
+		rating_markers,
+		is_adult,
+		status,
+	})
+}
+
+// Reading status the bookmark was put into: "Читаю", "В планах", etc.
+pub fn parse_bookmark_status<E: Element>(node: &E) -> Option<String> {
+	node.select("[class*=bookmark]")
+		.iter()
+		.map(|status_node| status_node.text().trim().to_string())
+		.find(|status| !status.is_empty())
+}
@@ -0,0 +1,489 @@
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+
+// Subset of JS literals found in the reader scripts: arrays, objects, strings, numbers,
+// booleans and null. Anything else (identifiers, calls, expressions) is kept as raw source.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsValue {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<JsValue>),
+	Object(Vec<(String, JsValue)>),
+	Raw(String),
+}
+
+impl JsValue {
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			JsValue::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	// String representation of scalar values, numbers and raw expressions included
+	pub fn to_plain_string(&self) -> Option<String> {
+		match self {
+			JsValue::String(s) | JsValue::Raw(s) => Some(s.clone()),
+			JsValue::Number(n) => Some(format!("{n}")),
+			JsValue::Bool(b) => Some(format!("{b}")),
+			JsValue::Null | JsValue::Array(_) | JsValue::Object(_) => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[JsValue]> {
+		match self {
+			JsValue::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	pub fn get(&self, key: &str) -> Option<&JsValue> {
+		match self {
+			JsValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+			_ => None,
+		}
+	}
+}
+
+struct Parser<'a> {
+	src: &'a str,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn new(src: &'a str) -> Self {
+		Parser { src, pos: 0 }
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.src[self.pos..]
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.rest().chars().next()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.pos += c.len_utf8();
+		Some(c)
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(c) = self.peek() {
+			if c.is_whitespace() {
+				self.bump();
+			} else if self.rest().starts_with("//") {
+				let line_len = self.rest().find('\n').unwrap_or(self.rest().len());
+				self.pos += line_len;
+			} else if self.rest().starts_with("/*") {
+				let comment_len = self.rest().find("*/").map_or(self.rest().len(), |i| i + 2);
+				self.pos += comment_len;
+			} else {
+				break;
+			}
+		}
+	}
+
+	fn eat(&mut self, expected: char) -> Option<()> {
+		self.skip_whitespace();
+		if self.peek()? == expected {
+			self.bump();
+			Some(())
+		} else {
+			None
+		}
+	}
+
+	fn parse_value(&mut self) -> Option<JsValue> {
+		self.skip_whitespace();
+		match self.peek()? {
+			'[' => self.parse_array(),
+			'{' => self.parse_object(),
+			'\'' | '"' | '`' => self.parse_string().map(JsValue::String),
+			c if c.is_ascii_digit() || c == '-' || c == '.' => {
+				let start = self.pos;
+				match self.parse_number() {
+					Some(n) => Some(JsValue::Number(n)),
+					None => {
+						self.pos = start;
+						self.parse_raw()
+					}
+				}
+			}
+			_ => self.parse_raw(),
+		}
+	}
+
+	fn parse_array(&mut self) -> Option<JsValue> {
+		self.eat('[')?;
+		let mut items = Vec::new();
+		loop {
+			self.skip_whitespace();
+			match self.peek()? {
+				']' => {
+					self.bump();
+					return Some(JsValue::Array(items));
+				}
+				',' => {
+					// elision or trailing comma
+					self.bump();
+				}
+				_ => {
+					items.push(self.parse_value()?);
+					self.skip_whitespace();
+					match self.bump()? {
+						',' => {}
+						']' => return Some(JsValue::Array(items)),
+						_ => return None,
+					}
+				}
+			}
+		}
+	}
+
+	fn parse_object(&mut self) -> Option<JsValue> {
+		self.eat('{')?;
+		let mut entries = Vec::new();
+		loop {
+			self.skip_whitespace();
+			let key = match self.peek()? {
+				'}' => {
+					self.bump();
+					return Some(JsValue::Object(entries));
+				}
+				'\'' | '"' => self.parse_string()?,
+				_ => {
+					let start = self.pos;
+					while self
+						.peek()
+						.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+					{
+						self.bump();
+					}
+					if start == self.pos {
+						return None;
+					}
+					self.src[start..self.pos].to_string()
+				}
+			};
+			self.eat(':')?;
+			entries.push((key, self.parse_value()?));
+			self.skip_whitespace();
+			match self.bump()? {
+				',' => {}
+				'}' => return Some(JsValue::Object(entries)),
+				_ => return None,
+			}
+		}
+	}
+
+	fn parse_string(&mut self) -> Option<String> {
+		let quote = self.bump()?;
+		let mut result = String::new();
+		loop {
+			match self.bump()? {
+				c if c == quote => return Some(result),
+				'\\' => match self.bump()? {
+					'n' => result.push('\n'),
+					't' => result.push('\t'),
+					'r' => result.push('\r'),
+					'b' => result.push('\u{8}'),
+					'f' => result.push('\u{c}'),
+					'v' => result.push('\u{b}'),
+					'0' => result.push('\0'),
+					'x' => result.push(self.parse_hex_char(2)?),
+					'u' => result.push(self.parse_unicode_escape()?),
+					'\n' => {}
+					c => result.push(c),
+				},
+				c => result.push(c),
+			}
+		}
+	}
+
+	fn parse_hex_char(&mut self, len: usize) -> Option<char> {
+		let code = self.parse_hex(len)?;
+		char::from_u32(code)
+	}
+
+	fn parse_hex(&mut self, len: usize) -> Option<u32> {
+		let hex = self.rest().get(..len)?;
+		let code = u32::from_str_radix(hex, 16).ok()?;
+		self.pos += len;
+		Some(code)
+	}
+
+	fn parse_unicode_escape(&mut self) -> Option<char> {
+		if self.peek()? == '{' {
+			self.bump();
+			let len = self.rest().find('}')?;
+			let code = self.parse_hex(len)?;
+			self.bump();
+			return char::from_u32(code);
+		}
+		let code = self.parse_hex(4)?;
+		if (0xD800..0xDC00).contains(&code) && self.rest().starts_with("\\u") {
+			// surrogate pair
+			let start = self.pos;
+			self.pos += 2;
+			match self.parse_hex(4) {
+				Some(low) if (0xDC00..0xE000).contains(&low) => {
+					return char::from_u32(0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00));
+				}
+				_ => self.pos = start,
+			}
+		}
+		Some(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+	}
+
+	fn parse_number(&mut self) -> Option<f64> {
+		let start = self.pos;
+		if self.peek() == Some('-') {
+			self.bump();
+		}
+		while self
+			.peek()
+			.is_some_and(|c| c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E')
+		{
+			let c = self.bump()?;
+			if (c == 'e' || c == 'E') && matches!(self.peek(), Some('+' | '-')) {
+				self.bump();
+			}
+		}
+		self.src[start..self.pos].parse().ok()
+	}
+
+	// Consumes an arbitrary expression up to the next top level `,` or closing bracket
+	fn parse_raw(&mut self) -> Option<JsValue> {
+		let start = self.pos;
+		let mut depth = 0usize;
+		while let Some(c) = self.peek() {
+			match c {
+				'(' | '[' | '{' => depth += 1,
+				')' | ']' | '}' if depth == 0 => break,
+				')' | ']' | '}' => depth -= 1,
+				',' | ';' if depth == 0 => break,
+				'\'' | '"' | '`' => {
+					self.parse_string()?;
+					continue;
+				}
+				_ => {}
+			}
+			self.bump();
+		}
+		let raw = self.src[start..self.pos].trim();
+		match raw {
+			"" => None,
+			"null" | "undefined" => Some(JsValue::Null),
+			"true" | "!0" => Some(JsValue::Bool(true)),
+			"false" | "!1" => Some(JsValue::Bool(false)),
+			_ => Some(JsValue::Raw(raw.to_string())),
+		}
+	}
+}
+
+// Parses a whole literal, like a JSON document, nothing but whitespace may follow it
+pub fn parse_literal(source: &str) -> Option<JsValue> {
+	let mut parser = Parser::new(source);
+	let value = parser.parse_value()?;
+	parser.skip_whitespace();
+	parser.rest().is_empty().then_some(value)
+}
+
+// Parses arguments of the first `callee(...)` call found in the script
+pub fn parse_call_arguments(script: &str, callee: &str) -> Option<Vec<JsValue>> {
+	let args_start = script.find(&format!("{callee}("))? + callee.len() + 1;
+	let mut parser = Parser::new(&script[args_start..]);
+	let mut args = Vec::new();
+	loop {
+		parser.skip_whitespace();
+		if parser.peek()? == ')' {
+			return Some(args);
+		}
+		args.push(parser.parse_value()?);
+		parser.skip_whitespace();
+		match parser.bump()? {
+			',' => {}
+			')' => return Some(args),
+			_ => return None,
+		}
+	}
+}
+
+// Parses value assigned to the first `name = ...` or `name: ...` found in the script
+pub fn parse_assignment(script: &str, name: &str) -> Option<JsValue> {
+	script.match_indices(name).find_map(|(idx, _)| {
+		let mut parser = Parser::new(&script[idx + name.len()..]);
+		parser.skip_whitespace();
+		match parser.bump()? {
+			'=' | ':' if parser.peek() != Some('=') => parser.parse_value(),
+			_ => None,
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn string(value: &str) -> JsValue {
+		JsValue::String(value.to_string())
+	}
+
+	#[test]
+	fn parses_nested_arrays() {
+		let args = parse_call_arguments("f([[1, [2, []]], ['a']]);", "f").unwrap();
+		assert_eq!(
+			args,
+			vec![JsValue::Array(vec![
+				JsValue::Array(vec![
+					JsValue::Number(1.0),
+					JsValue::Array(vec![JsValue::Number(2.0), JsValue::Array(vec![])]),
+				]),
+				JsValue::Array(vec![string("a")]),
+			])]
+		);
+	}
+
+	#[test]
+	fn parses_quotes_and_escapes() {
+		let args = parse_call_arguments(
+			r#"f('single', "double", 'it\'s', "say \"hi\"", 'mixed "quotes"', `tpl`, "Г\x41\n");"#,
+			"f",
+		)
+		.unwrap();
+		assert_eq!(
+			args,
+			vec![
+				string("single"),
+				string("double"),
+				string("it's"),
+				string("say \"hi\""),
+				string("mixed \"quotes\""),
+				string("tpl"),
+				string("ГA\n"),
+			]
+		);
+	}
+
+	#[test]
+	fn parses_surrogate_pairs() {
+		let args = parse_call_arguments(r#"f("😀", "\u{1F600}");"#, "f").unwrap();
+		assert_eq!(args, vec![string("😀"), string("😀")]);
+	}
+
+	#[test]
+	fn accepts_trailing_commas() {
+		let args = parse_call_arguments("f([1, 2,], {a: 1, 'b': 2,}, );", "f").unwrap();
+		assert_eq!(
+			args,
+			vec![
+				JsValue::Array(vec![JsValue::Number(1.0), JsValue::Number(2.0)]),
+				JsValue::Object(vec![
+					("a".to_string(), JsValue::Number(1.0)),
+					("b".to_string(), JsValue::Number(2.0)),
+				]),
+			]
+		);
+	}
+
+	#[test]
+	fn parses_keywords_and_expressions() {
+		let args = parse_call_arguments(
+			"f(false, null, true, undefined, !0, !1, a.b(1, 2), -1.5e3);",
+			"f",
+		)
+		.unwrap();
+		assert_eq!(
+			args,
+			vec![
+				JsValue::Bool(false),
+				JsValue::Null,
+				JsValue::Bool(true),
+				JsValue::Null,
+				JsValue::Bool(true),
+				JsValue::Bool(false),
+				JsValue::Raw("a.b(1, 2)".to_string()),
+				JsValue::Number(-1500.0),
+			]
+		);
+	}
+
+	#[test]
+	fn skips_comments() {
+		let args = parse_call_arguments("f(/* first */ 1, // second\n 2);", "f").unwrap();
+		assert_eq!(args, vec![JsValue::Number(1.0), JsValue::Number(2.0)]);
+	}
+
+	#[test]
+	fn reads_object_fields() {
+		let value = parse_assignment(
+			r#"var other = 1; var servers = [{"path":"https://t1.rmr.rocks/","res":false}];"#,
+			"servers",
+		)
+		.unwrap();
+		let server = &value.as_array().unwrap()[0];
+		assert_eq!(
+			server.get("path").and_then(JsValue::as_str),
+			Some("https://t1.rmr.rocks/")
+		);
+		assert_eq!(server.get("res"), Some(&JsValue::Bool(false)));
+		assert_eq!(server.get("missing"), None);
+	}
+
+	#[test]
+	fn skips_comparisons() {
+		let value = parse_assignment("if (servers == null) {} servers = [1];", "servers");
+		assert_eq!(value, Some(JsValue::Array(vec![JsValue::Number(1.0)])));
+	}
+
+	#[test]
+	fn converts_scalars_to_plain_strings() {
+		assert_eq!(
+			JsValue::Number(1000.0).to_plain_string(),
+			Some("1000".to_string())
+		);
+		assert_eq!(string("a").to_plain_string(), Some("a".to_string()));
+		assert_eq!(
+			JsValue::Raw("x".to_string()).to_plain_string(),
+			Some("x".to_string())
+		);
+		assert_eq!(JsValue::Null.to_plain_string(), None);
+	}
+
+	#[test]
+	fn parses_whole_literals() {
+		assert_eq!(
+			parse_literal(r#" {"ct": "abc", "s": "0a"} "#),
+			Some(JsValue::Object(vec![
+				("ct".to_string(), string("abc")),
+				("s".to_string(), string("0a")),
+			]))
+		);
+		assert_eq!(parse_literal(r#""[\"a\"]""#), Some(string(r#"["a"]"#)));
+		assert_eq!(parse_literal("[1] 2"), None);
+	}
+
+	#[test]
+	fn fails_on_unterminated_input() {
+		for script in [
+			"f(",
+			"f([1, 2",
+			"f('abc",
+			"f({a: 1",
+			"f([1, 2]",
+			"f(\"\\u00",
+			"f([1 2])",
+		] {
+			assert_eq!(parse_call_arguments(script, "f"), None, "{script}");
+		}
+		assert_eq!(parse_call_arguments("g(1);", "f"), None);
+	}
+}
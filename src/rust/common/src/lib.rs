@@ -0,0 +1,18 @@
+#![cfg_attr(not(test), no_std)]
+// Parsing shared by the Rust sources. Nothing here calls into the Aidoku runtime, so the crate
+// builds for the host and is tested with a plain `cargo test`
+extern crate alloc;
+
+pub mod chapter_label;
+pub mod cookies;
+pub mod cryptojs;
+pub mod dates;
+pub mod dom;
+pub mod grouple_reader;
+pub mod grouple_search_form;
+pub mod grouple_tile;
+pub mod js_literal;
+pub mod madara_chapters;
+pub mod madara_protector;
+pub mod madara_search_form;
+pub mod search_form;
@@ -0,0 +1,96 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{chapter_label::parse_chapter_label, dom::Element};
+
+fn select_first<E: Element>(node: &E, selector: &str) -> Option<E> {
+	node.select(selector).into_iter().next()
+}
+
+// The list rendered into the title page by older Madara versions
+pub fn find_inline_chapter_list<E: Element>(html: &E) -> Option<E> {
+	select_first(
+		html,
+		"div.page-content-listing.single-page ul.main.version-chap",
+	)
+}
+
+// Both AJAX endpoints answer with the listing alone. A list without chapters is a valid answer
+// for a title which has none yet, only a response without the list means the endpoint is gone
+pub fn find_ajax_chapter_list<E: Element>(response: &E) -> Option<E> {
+	select_first(response, "ul.main.version-chap")
+}
+
+// Post id of the title, which the legacy `admin-ajax.php` endpoint takes instead of its url
+pub fn parse_manga_data_id<E: Element>(html: &E) -> Option<String> {
+	select_first(html, "div.c-page-content div#manga-chapters-holder")?.attr("data-id")
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChapterRow {
+	pub url: String,
+	pub label: String,
+	// volume of the "Том N" group the chapter is listed under
+	pub group_volume: Option<f32>,
+	// old chapters show the date as text, new ones only have it in the "new" tag title, so
+	// these are the candidates in order of preference
+	pub release_dates: Vec<String>,
+}
+
+// Rows of the list from the newest chapter to the oldest one; chapters may be grouped under
+// "Том N" headers, each group keeps its chapters in `ul.sub-chap`
+pub fn parse_chapter_rows<E: Element>(list_node: &E) -> Vec<ChapterRow> {
+	let group_volumes: Vec<(String, f32)> = list_node
+		.select("li.parent.has-child")
+		.iter()
+		.filter_map(|group_node| {
+			let volume =
+				parse_chapter_label(&select_first(group_node, "a.has-child")?.text()).volume?;
+			Some(
+				group_node
+					.select("ul.sub-chap li.wp-manga-chapter a")
+					.iter()
+					.filter_map(|url_node| url_node.attr("href"))
+					.map(|url| (url, volume))
+					.collect::<Vec<_>>(),
+			)
+		})
+		.flatten()
+		.collect();
+
+	list_node
+		.select("li.wp-manga-chapter")
+		.iter()
+		.filter_map(|chapter_node| {
+			let url_node = select_first(chapter_node, "a")?;
+			let url = url_node.attr("href")?;
+			let group_volume = group_volumes
+				.iter()
+				.find(|(group_url, _)| *group_url == url)
+				.map(|(_, volume)| *volume);
+			let release_dates = select_first(chapter_node, "span.chapter-release-date")
+				.map(|release_date_node| {
+					let mut release_dates: Vec<_> = [
+						select_first(&release_date_node, "i").map(|i_node| i_node.text()),
+						select_first(&release_date_node, "a")
+							.and_then(|a_node| a_node.attr("title")),
+						Some(release_date_node.text()),
+					]
+					.into_iter()
+					.flatten()
+					.map(|date| String::from(date.trim()))
+					.filter(|date| !date.is_empty())
+					.collect();
+					// the text of the span is mostly the text of its `i`
+					release_dates.dedup();
+					release_dates
+				})
+				.unwrap_or_default();
+			Some(ChapterRow {
+				url,
+				label: String::from(url_node.text().trim()),
+				group_volume,
+				release_dates,
+			})
+		})
+		.collect()
+}
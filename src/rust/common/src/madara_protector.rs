@@ -0,0 +1,149 @@
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+use crate::{
+	cryptojs,
+	dom::Element,
+	js_literal::{self, JsValue},
+};
+
+fn parse_string_array(value: &JsValue) -> Option<Vec<String>> {
+	value
+		.as_array()?
+		.iter()
+		.map(|item| item.as_str().map(|url| url.trim().to_string()))
+		.filter(|url| url.as_ref().is_none_or(|url| !url.is_empty()))
+		.collect()
+}
+
+// `chapter_data` is the CryptoJS JSON format: base64 ciphertext `ct`, hex salt `s` and `iv`,
+// which CryptoJS ignores since key and iv are both derived from the password
+fn decrypt_chapter_data(chapter_data: &str, password: &str) -> Option<String> {
+	let chapter_data = js_literal::parse_literal(chapter_data)?;
+	let ciphertext = cryptojs::base64_decode(chapter_data.get("ct")?.as_str()?)?;
+	let salt = cryptojs::hex_decode(chapter_data.get("s")?.as_str()?)?;
+	let plaintext = cryptojs::decrypt(&ciphertext, &salt, password)?;
+	String::from_utf8(plaintext).ok()
+}
+
+// The protector script of a chapter page; older versions print it without the id, other scripts
+// of the page may mention `chapter_data` too, so the password has to be next to it
+pub fn find_protector_script<E: Element>(html: &E) -> Option<String> {
+	html.select("script#chapter-protector-data")
+		.first()
+		.map(Element::data)
+		.or_else(|| {
+			html.select("script")
+				.iter()
+				.map(Element::data)
+				.find(|script| {
+					script.contains("chapter_data") && script.contains("wpmangaprotectornonce")
+				})
+		})
+}
+
+// Chapters with the protector enabled have no images in the markup, the urls are in an
+// encrypted JSON array of the `chapter-protector-data` script, next to its password
+pub fn decrypt_image_urls(script: &str) -> Option<Vec<String>> {
+	let chapter_data = js_literal::parse_assignment(script, "chapter_data")?;
+	let password = js_literal::parse_assignment(script, "wpmangaprotectornonce")?;
+	let decrypted = decrypt_chapter_data(chapter_data.as_str()?, password.as_str()?)?;
+
+	// the array is usually encoded twice, so it comes as a JSON string
+	let urls = match js_literal::parse_literal(&decrypted)? {
+		JsValue::String(inner_json) => js_literal::parse_literal(&inner_json)?,
+		urls => urls,
+	};
+	parse_string_array(&urls)
+}
+
+// Some versions of the protector keep the list unencrypted in `chapter_preloaded_images`
+pub fn parse_preloaded_image_urls(script: &str) -> Option<Vec<String>> {
+	parse_string_array(&js_literal::parse_assignment(
+		script,
+		"chapter_preloaded_images",
+	)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Generated with `openssl enc -aes-256-cbc -md md5 -pass pass:9c2f0a1b3d -S 5d8e2a7c91b4f036`
+	// from a doubly JSON encoded image list and laid out the way the protector script prints it,
+	// slashes escaped by PHP included
+	const CIPHERTEXT: &str = "aQNdohIa4xDxqiViFcDWzA4AQ\\/S\\/jb5ifr8eJ2lVitm+AQEm9+8SCtlx8bOJgwbtyUl\\/ftqEVhYD\\/1tZr1i6ccXpND8+m4BdrfgoMu0Wo0vhtRxs4ZGwLPDDxKGERsTSZMvZFYpT+9toIcY2GEJjKxK6SsaqsoWe8Zr8ZSuzXaldqzxuv4eG\\/DJZ1KXPEpqTK8fm\\/CieSVs5GxdY6\\/WN0CD1hjticDktt+qXZVYEqW21rs1uYMfye8tmNEXM8u4KVSr4gvgzqb3\\/6R\\/JmSi73LZ++kRtQFLrUSKqYKB4MsY=";
+	const IV: &str = "a379bd9cb05524cd0b7e8305dccf43fd";
+	const SALT: &str = "5d8e2a7c91b4f036";
+	const NONCE: &str = "9c2f0a1b3d";
+
+	fn protector_script(chapter_data: &str, nonce: &str) -> String {
+		format!("var chapter_data = '{chapter_data}';\nvar wpmangaprotectornonce = '{nonce}';")
+	}
+
+	fn chapter_data(fields: &[(&str, &str)]) -> String {
+		let fields: Vec<_> = fields
+			.iter()
+			.map(|(key, value)| format!("\"{key}\":\"{value}\""))
+			.collect();
+		format!("{{{}}}", fields.join(","))
+	}
+
+	#[test]
+	fn decrypts_protected_image_urls() {
+		let script = protector_script(
+			&chapter_data(&[("ct", CIPHERTEXT), ("iv", IV), ("s", SALT)]),
+			NONCE,
+		);
+		assert_eq!(
+			decrypt_image_urls(&script),
+			Some(vec![
+				"https://mangaonelove.site/wp-content/uploads/WP-manga/data/manga_64f1/ch_12/01.jpg"
+					.to_string(),
+				"https://mangaonelove.site/wp-content/uploads/WP-manga/data/manga_64f1/ch_12/02.jpg"
+					.to_string(),
+			])
+		);
+	}
+
+	#[test]
+	fn rejects_wrong_nonce() {
+		let script = protector_script(
+			&chapter_data(&[("ct", CIPHERTEXT), ("iv", IV), ("s", SALT)]),
+			"0000000000",
+		);
+		assert_eq!(decrypt_image_urls(&script), None);
+	}
+
+	#[test]
+	fn rejects_malformed_chapter_data() {
+		let overpadded_ciphertext = format!("{CIPHERTEXT}=");
+		for fields in [
+			[("ct", CIPHERTEXT), ("iv", IV), ("x", SALT)],
+			[("x", CIPHERTEXT), ("iv", IV), ("s", SALT)],
+			[("ct", "aQNd*hIa"), ("iv", IV), ("s", SALT)],
+			[("ct", &overpadded_ciphertext), ("iv", IV), ("s", SALT)],
+			[("ct", CIPHERTEXT), ("iv", IV), ("s", "5d8e2a7c91b4f03")],
+		] {
+			let script = protector_script(&chapter_data(&fields), NONCE);
+			assert_eq!(decrypt_image_urls(&script), None, "{fields:?}");
+		}
+		assert_eq!(decrypt_image_urls("var chapter_data = '{';"), None);
+		assert_eq!(decrypt_image_urls("var wpmangaprotectornonce = 'a';"), None);
+	}
+
+	#[test]
+	fn parses_preloaded_image_urls() {
+		let script = r#"var chapter_preloaded_images = ["https:\/\/a.site\/1.jpg", " https://a.site/2.jpg ", ""], chapter_images_per_page = 1;"#;
+		assert_eq!(
+			parse_preloaded_image_urls(script),
+			Some(vec![
+				"https://a.site/1.jpg".to_string(),
+				"https://a.site/2.jpg".to_string(),
+			])
+		);
+		assert_eq!(parse_preloaded_image_urls("var chapter_data = '';"), None);
+	}
+}
@@ -0,0 +1,22 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::dom::Element;
+
+// Label and slug of every genre of the Madara search form; the checkboxes are
+// `<input name="genre[]" value="<slug>" id="<slug>">` with a separate `<label for="<slug>">`
+pub fn parse_genre_slugs<E: Element>(html: &E) -> Vec<(String, String)> {
+	html.select("input[name='genre[]']")
+		.iter()
+		.filter_map(|input_node| {
+			let slug = input_node.attr("value").filter(|slug| !slug.is_empty())?;
+			let id = input_node.attr("id").unwrap_or_else(|| slug.clone());
+			let name = html
+				.select(&format!("label[for='{id}']"))
+				.first()?
+				.text()
+				.trim()
+				.to_lowercase();
+			(!name.is_empty()).then_some((name, slug))
+		})
+		.collect()
+}
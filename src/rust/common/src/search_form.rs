@@ -0,0 +1,11 @@
+use alloc::string::String;
+
+// Search forms are read into (label, parameter) pairs with lowercased labels. Labels are compared
+// ignoring case, since the form and the filter list capitalize differently
+pub fn find_by_label<'a>(options: &'a [(String, String)], label: &str) -> Option<&'a str> {
+	let label = label.trim().to_lowercase();
+	options
+		.iter()
+		.find(|(option_label, _)| *option_label == label)
+		.map(|(_, parameter)| parameter.as_str())
+}
@@ -0,0 +1,170 @@
+// Every Grouple site is checked against pages in its own markup, reduced to what the parsers read.
+// The pages are reconstructed from the sites rather than recorded, see the note in each fixture
+mod support;
+
+use common::{
+	dom::Element,
+	grouple_reader::{parse_reader_page, ReaderPage},
+	grouple_search_form::{is_element_id, parse_filter_ids},
+	grouple_tile::{parse_tile, Tile, TileStatus},
+	search_form::find_by_label,
+};
+use support::{fixture, Document};
+
+fn parse_search_page(site: &str) -> Vec<Tile> {
+	let document = Document::parse(&fixture(&format!("grouple/{site}/search.html")));
+	let tile_nodes = document.root().select("div.tile");
+	let tiles: Vec<_> = tile_nodes.iter().filter_map(parse_tile).collect();
+	assert_eq!(
+		tiles.len(),
+		tile_nodes.len(),
+		"{site}: every tile is parsed"
+	);
+	tiles
+}
+
+fn summary(tile: &Tile) -> (&str, &str, bool, TileStatus) {
+	(&tile.id, &tile.title, tile.is_adult, tile.status)
+}
+
+#[test]
+fn parses_readmanga_search() {
+	let tiles = parse_search_page("readmanga");
+	assert_eq!(
+		tiles.iter().map(summary).collect::<Vec<_>>(),
+		vec![
+			("van_pis", "Ван-Пис", false, TileStatus::Ongoing),
+			(
+				"povsednevnost",
+				"Повседневность",
+				false,
+				TileStatus::Completed
+			),
+		]
+	);
+	assert_eq!(
+		tiles[0].categories,
+		vec!["сёнэн", "приключения", "комедия", "пираты"]
+	);
+	assert_eq!(tiles[0].authors, vec!["Ода Эйитиро"]);
+}
+
+#[test]
+fn parses_mintmanga_search() {
+	let tiles = parse_search_page("mintmanga");
+	assert_eq!(
+		tiles.iter().map(summary).collect::<Vec<_>>(),
+		vec![
+			("sun_ken_rock", "Скала Сонкена", true, TileStatus::Completed),
+			(
+				"killing_stalking",
+				"Убить сталкера",
+				true,
+				TileStatus::Ongoing
+			),
+		]
+	);
+	assert_eq!(
+		tiles[1].rating_markers,
+		vec!["Манхва", "триллер", "яой", "18+"]
+	);
+	assert_eq!(tiles[1].description, "");
+}
+
+#[test]
+fn parses_selfmanga_search() {
+	let tiles = parse_search_page("selfmanga");
+	assert_eq!(
+		tiles.iter().map(summary).collect::<Vec<_>>(),
+		vec![("maior_grom", "Майор Гром", false, TileStatus::Ongoing)]
+	);
+	assert_eq!(
+		tiles[0].authors,
+		vec!["Артём Габрелянов", "Евгений Федотов"]
+	);
+	assert_eq!(
+		tiles[0].cover,
+		"https://staticsm.rmr.rocks/uploads/pics/00/01/37_p.jpg"
+	);
+}
+
+#[test]
+fn parses_usagi_search() {
+	let tiles = parse_search_page("usagi");
+	assert_eq!(
+		tiles.iter().map(summary).collect::<Vec<_>>(),
+		vec![
+			(
+				"vanitas_no_carte",
+				"Мемуары Ванитаса",
+				false,
+				TileStatus::Ongoing
+			),
+			("kaiju_8", "Кайдзю номер восемь", false, TileStatus::Unknown),
+		]
+	);
+	assert_eq!(tiles[1].rating_markers, vec!["боевик", "16+"]);
+}
+
+#[test]
+fn reads_filter_ids_referenced_by_labels() {
+	let document = Document::parse(&fixture("grouple/readmanga/advanced_search.html"));
+	let filter_ids = parse_filter_ids(&document.root());
+	assert_eq!(
+		filter_ids,
+		vec![
+			("боевик".to_string(), "el_2155".to_string()),
+			("комедия".to_string(), "el_2136".to_string()),
+			("манхва".to_string(), "el_3001".to_string()),
+		]
+	);
+	assert_eq!(find_by_label(&filter_ids, "Комедия"), Some("el_2136"));
+	assert_eq!(find_by_label(&filter_ids, "Название"), None);
+}
+
+#[test]
+fn reads_filter_ids_of_nested_inputs() {
+	let document = Document::parse(&fixture("grouple/usagi/advanced_search.html"));
+	let filter_ids = parse_filter_ids(&document.root());
+	assert_eq!(find_by_label(&filter_ids, "боевик"), Some("el_5032"));
+	assert_eq!(find_by_label(&filter_ids, "сёнэн"), Some("el_5040"));
+	assert_eq!(find_by_label(&filter_ids, "В цвете"), Some("el_5101"));
+	// filters the site doesn't offer are not guessed
+	assert_eq!(find_by_label(&filter_ids, "яой"), None);
+	assert_eq!(filter_ids.len(), 4);
+}
+
+#[test]
+fn tells_element_ids_from_fixed_parameters() {
+	assert!(is_element_id("el_2155"));
+	assert!(!is_element_id("s_translated"));
+	assert!(!is_element_id("prod_FINISHED"));
+}
+
+#[test]
+fn parses_readmanga_reader_page() {
+	let document = Document::parse(&fixture("grouple/readmanga/chapter.html"));
+	let data = parse_reader_page(&document.root()).expect("reader script");
+	let sources: Vec<_> = data.pages.iter().map(ReaderPage::server_and_path).collect();
+	assert_eq!(
+		sources,
+		vec![
+			(
+				"https://one-way.work/",
+				"auto/68/83/46/01.jpg_res.jpg?t=1716000000&u=0&h=Ab,Cd".to_string()
+			),
+			(
+				"https://t1.rmr.rocks/",
+				"vol1/1/02_\"final\".png".to_string()
+			),
+			(
+				"https://t2.rmr.rocks/",
+				"auto/68/83/46/03,04.jpg".to_string()
+			),
+		]
+	);
+	assert_eq!(
+		data.servers,
+		vec!["https://t1.rmr.rocks/", "https://t2.rmr.rocks/"]
+	);
+}
@@ -0,0 +1,79 @@
+mod support;
+
+use common::dom::Element;
+
+use common::grouple_tile::{parse_bookmark_status, parse_tile, Tile, TileStatus};
+use support::{fixture, Document};
+
+fn strings(values: &[&str]) -> Vec<String> {
+	values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn parses_bookmark_tiles() {
+	let document = Document::parse(&fixture("grouple/bookmarks.html"));
+	let tile_nodes = document.root().select("div.tile");
+	assert_eq!(tile_nodes.len(), 4);
+
+	let tiles: Vec<_> = tile_nodes.iter().filter_map(parse_tile).collect();
+	assert_eq!(
+		tiles,
+		vec![
+			Tile {
+				id: "berserk".to_string(),
+				cover: "https://staticrm.rmr.rocks/uploads/pics/00/00/27_p.jpg".to_string(),
+				title: "Берсерк".to_string(),
+				authors: strings(&["Миура Кэнтаро"]),
+				description: "Гатс, Чёрный мечник, ищет мести.".to_string(),
+				categories: strings(&["сэйнэн", "боевик", "драма", "демоны"]),
+				rating_markers: strings(&["сэйнэн", "боевик", "драма", "демоны"]),
+				is_adult: false,
+				status: TileStatus::Ongoing,
+			},
+			Tile {
+				id: "ubiica_goblinov".to_string(),
+				cover: "https://staticrm.rmr.rocks/uploads/pics/00/56/78_p.jpg".to_string(),
+				title: "Убийца гоблинов".to_string(),
+				authors: strings(&["Кумо Кагю", "Куросэ Косукэ"]),
+				description: "Жрица вступает в группу, которая охотится только на гоблинов."
+					.to_string(),
+				categories: strings(&["фэнтези"]),
+				rating_markers: strings(&["фэнтези", "18+"]),
+				is_adult: true,
+				status: TileStatus::Completed,
+			},
+			Tile {
+				id: "klinok__rassekaiuchii_demonov__antologiia".to_string(),
+				cover: "https://staticrm.rmr.rocks/uploads/pics/00/90/12_p.jpg".to_string(),
+				title: "Клинок, рассекающий демонов: Антология".to_string(),
+				authors: Vec::new(),
+				description: String::new(),
+				categories: Vec::new(),
+				rating_markers: Vec::new(),
+				is_adult: false,
+				status: TileStatus::Unknown,
+			},
+		]
+	);
+}
+
+#[test]
+fn parses_bookmark_statuses() {
+	let document = Document::parse(&fixture("grouple/bookmarks.html"));
+	let statuses: Vec<_> = document
+		.root()
+		.select("div.tile")
+		.iter()
+		.map(parse_bookmark_status)
+		.collect();
+	assert_eq!(
+		statuses,
+		vec![
+			Some("Читаю".to_string()),
+			// the empty `bookmark-actions` block comes first and is skipped
+			Some("В планах".to_string()),
+			None,
+			None,
+		]
+	);
+}
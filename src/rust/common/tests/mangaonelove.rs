@@ -0,0 +1,146 @@
+// MangaOneLove runs on Madara, the fixtures are its pages reduced to the markup the parsers read.
+// They are reconstructed from the Madara markup rather than recorded
+mod support;
+
+use common::{
+	madara_chapters::{
+		find_ajax_chapter_list, find_inline_chapter_list, parse_chapter_rows, parse_manga_data_id,
+		ChapterRow,
+	},
+	madara_protector::{decrypt_image_urls, find_protector_script},
+	madara_search_form::parse_genre_slugs,
+	search_form::find_by_label,
+};
+use support::{fixture, Document};
+
+const MANGA_URL: &str = "https://mangaonelove.site/manga/solo-leveling";
+
+fn parse_page(name: &str) -> Document {
+	Document::parse(&fixture(&format!("mangaonelove/{name}.html")))
+}
+
+fn row(path: &str, label: &str, group_volume: Option<f32>, release_dates: &[&str]) -> ChapterRow {
+	ChapterRow {
+		url: format!("{MANGA_URL}/{path}/"),
+		label: label.to_string(),
+		group_volume,
+		release_dates: release_dates.iter().map(ToString::to_string).collect(),
+	}
+}
+
+// the AJAX responses are fragments, the runtime parses them as a document as well
+fn parse_ajax_rows(name: &str) -> Option<Vec<ChapterRow>> {
+	let document = parse_page(name);
+	find_ajax_chapter_list(&document.root()).map(|list_node| parse_chapter_rows(&list_node))
+}
+
+#[test]
+fn parses_genre_slugs() {
+	let document = Document::parse(&fixture("mangaonelove/search.html"));
+	let genre_slugs = parse_genre_slugs(&document.root());
+	assert_eq!(
+		genre_slugs,
+		vec![
+			("боевик".to_string(), "boevik".to_string()),
+			(
+				"боевые искусства".to_string(),
+				"boevye-iskusstva".to_string()
+			),
+			("сёдзё".to_string(), "sedze".to_string()),
+			("яой".to_string(), "yaoi".to_string()),
+		]
+	);
+	assert_eq!(find_by_label(&genre_slugs, "Сёдзё"), Some("sedze"));
+	assert_eq!(find_by_label(&genre_slugs, "яой"), Some("yaoi"));
+	// status checkboxes are not genres
+	assert_eq!(find_by_label(&genre_slugs, "Онгоинг"), None);
+}
+
+#[test]
+fn parses_inline_chapter_list() {
+	let document = parse_page("title");
+	let list_node = find_inline_chapter_list(&document.root()).expect("inline list");
+	assert_eq!(
+		parse_chapter_rows(&list_node),
+		vec![
+			row(
+				"tom-2/glava-3",
+				"Глава 3 - Начало",
+				Some(2.0),
+				&["2 дня назад"]
+			),
+			row("tom-2/ekstra-1", "Экстра 1", Some(2.0), &["15 марта 2024"]),
+			row("tom-1/glava-2", "Глава 2", Some(1.0), &["1 марта 2024"]),
+			row("tom-1/glava-1", "Глава 1", Some(1.0), &["1 марта 2024"]),
+		]
+	);
+}
+
+#[test]
+fn falls_back_to_ajax_without_inline_list() {
+	let document = parse_page("title_ajax");
+	assert!(find_inline_chapter_list(&document.root()).is_none());
+	assert_eq!(
+		parse_manga_data_id(&document.root()).as_deref(),
+		Some("15432")
+	);
+}
+
+#[test]
+fn parses_manga_ajax_chapter_list() {
+	assert_eq!(
+		parse_ajax_rows("ajax_chapters"),
+		Some(vec![
+			row("glava-101", "Глава 101 (часть 2)", None, &["вчера"]),
+			row(
+				"glava-101-1",
+				"Глава 101 (часть 1)",
+				None,
+				&["20 марта 2024"]
+			),
+		])
+	);
+}
+
+#[test]
+fn parses_admin_ajax_chapter_list() {
+	assert_eq!(
+		parse_ajax_rows("admin_ajax_chapters"),
+		Some(vec![row("glava-100", "100", None, &["10 марта 2024"])])
+	);
+}
+
+#[test]
+fn accepts_empty_ajax_chapter_list() {
+	assert_eq!(parse_ajax_rows("ajax_chapters_empty"), Some(vec![]));
+}
+
+#[test]
+fn rejects_ajax_responses_without_list() {
+	assert_eq!(parse_ajax_rows("ajax_not_found"), None);
+	assert_eq!(parse_ajax_rows("admin_ajax_unknown_action"), None);
+}
+
+#[test]
+fn decrypts_protected_chapter_page() {
+	let html = fixture("mangaonelove/chapter_protected.html");
+	let expected_urls = Some(vec![
+		"https://mangaonelove.site/wp-content/uploads/WP-manga/data/manga_64f1/ch_12/01.jpg"
+			.to_string(),
+		"https://mangaonelove.site/wp-content/uploads/WP-manga/data/manga_64f1/ch_12/02.jpg"
+			.to_string(),
+	]);
+
+	let script = find_protector_script(&Document::parse(&html).root()).expect("protector script");
+	assert_eq!(decrypt_image_urls(&script), expected_urls);
+
+	// without the id the script is told from the localized `manga` object by its password
+	let html = html.replace(r#" id="chapter-protector-data""#, "");
+	let script = find_protector_script(&Document::parse(&html).root()).expect("protector script");
+	assert_eq!(decrypt_image_urls(&script), expected_urls);
+}
+
+#[test]
+fn finds_no_protector_on_plain_pages() {
+	assert_eq!(find_protector_script(&parse_page("title").root()), None);
+}
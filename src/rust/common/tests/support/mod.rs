@@ -0,0 +1,52 @@
+// `Element` over a host HTML parser, so the shared parsers can be run against saved pages
+use common::dom::Element;
+use scraper::{ElementRef, Html, Selector};
+
+pub struct Document(Html);
+
+impl Document {
+	pub fn parse(html: &str) -> Self {
+		Document(Html::parse_document(html))
+	}
+
+	pub fn root(&self) -> TestElement<'_> {
+		TestElement(self.0.root_element())
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct TestElement<'a>(ElementRef<'a>);
+
+impl Element for TestElement<'_> {
+	// like the Aidoku runtime, the element itself is matched too
+	fn select(&self, selector: &str) -> Vec<Self> {
+		let selector = Selector::parse(selector).expect("valid selector");
+		let itself = selector.matches(&self.0).then_some(*self);
+		itself
+			.into_iter()
+			.chain(self.0.select(&selector).map(TestElement))
+			.collect()
+	}
+
+	fn attr(&self, name: &str) -> Option<String> {
+		self.0.value().attr(name).map(str::to_string)
+	}
+
+	fn text(&self) -> String {
+		self.0
+			.text()
+			.collect::<String>()
+			.split_whitespace()
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	fn data(&self) -> String {
+		self.0.text().collect()
+	}
+}
+
+pub fn fixture(path: &str) -> String {
+	let path = format!("{}/tests/fixtures/{path}", env!("CARGO_MANIFEST_DIR"));
+	std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{path}: {err}"))
+}
@@ -0,0 +1,30 @@
+#!/usr/bin/env bash
+# template source build script
+# usage: ./build.sh [source_name/-a]
+
+package_source() {
+	echo "packaging $1"
+	mkdir -p target/wasm32-unknown-unknown/release/Payload
+	cp sources/$1/res/* target/wasm32-unknown-unknown/release/Payload
+	(
+		cd target/wasm32-unknown-unknown/release
+		cp $1.wasm Payload/main.wasm
+		zip -r package.aix Payload >> /dev/null
+		mv package.aix ../../../$1.aix
+		rm -rf Payload
+	)
+}
+
+cargo +nightly build --release
+
+if [ "$1" != "-a" ] && [ "$1" != "" ]; then
+	# compile specific source
+	package_source $1
+else
+	# compile all sources
+	for dir in sources/*/
+	do
+		dir=${dir%*/}
+		package_source ${dir##*/}
+	done
+fi
@@ -0,0 +1,52 @@
+#![no_std]
+use aidoku::{
+	error::Result,
+	prelude::*,
+	std::{net::Request, String, Vec},
+	Chapter, DeepLink, Filter, Listing, Manga, MangaContentRating, MangaPageResult, Page,
+};
+use grouple_template::template::{self, GroupleSiteData};
+
+const DATA: GroupleSiteData = GroupleSiteData {
+	content_rating: MangaContentRating::Safe,
+};
+
+#[get_manga_list]
+fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult> {
+	template::get_manga_list(filters, page, &DATA)
+}
+
+#[get_manga_listing]
+fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	template::get_manga_listing(listing, page, &DATA)
+}
+
+#[get_manga_details]
+fn get_manga_details(manga_id: String) -> Result<Manga> {
+	template::get_manga_details(manga_id, &DATA)
+}
+
+#[get_chapter_list]
+fn get_chapter_list(manga_id: String) -> Result<Vec<Chapter>> {
+	template::get_chapter_list(manga_id)
+}
+
+#[get_page_list]
+fn get_page_list(manga_id: String, chapter_id: String) -> Result<Vec<Page>> {
+	template::get_page_list(manga_id, chapter_id)
+}
+
+#[modify_image_request]
+fn modify_image_request(request: Request) {
+	template::modify_image_request(request)
+}
+
+#[handle_notification]
+fn handle_notification(notification: String) {
+	template::handle_notification(notification)
+}
+
+#[handle_url]
+fn handle_url(url: String) -> Result<DeepLink> {
+	template::handle_url(url, &DATA)
+}
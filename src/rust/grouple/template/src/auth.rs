@@ -0,0 +1,101 @@
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind, Result},
+	helpers::uri::encode_uri,
+	prelude::*,
+	std::{
+		defaults::{defaults_get, defaults_set},
+		net::{HttpMethod, Request},
+		String, StringRef,
+	},
+};
+use alloc::string::ToString;
+use common::cookies;
+
+use crate::{
+	helpers::get_base_url,
+	wrappers::{debug, WNode},
+};
+
+const USERNAME_KEY: &str = "username";
+const PASSWORD_KEY: &str = "password";
+const SESSION_COOKIE_KEY: &str = "sessionCookie";
+
+fn get_string_setting(key: &str) -> String {
+	defaults_get(key)
+		.and_then(|x| x.as_string())
+		.map(|x| x.read().trim().to_string())
+		.unwrap_or_default()
+}
+
+fn set_session_cookie(cookie: &str) {
+	defaults_set(SESSION_COOKIE_KEY, StringRef::from(cookie).0);
+}
+
+pub fn get_session_cookie() -> Option<String> {
+	Some(get_string_setting(SESSION_COOKIE_KEY)).filter(|cookie| !cookie.is_empty())
+}
+
+pub fn login() {
+	let username = get_string_setting(USERNAME_KEY);
+	let password = get_string_setting(PASSWORD_KEY);
+
+	if username.is_empty() || password.is_empty() {
+		set_session_cookie("");
+		return;
+	}
+
+	let request = Request::new(
+		format!("{}/login/authenticate", get_base_url()),
+		HttpMethod::Post,
+	)
+	.header("Content-Type", "application/x-www-form-urlencoded")
+	.header("Referer", format!("{}/login", get_base_url()).as_str())
+	.body(format!(
+		"username={}&password={}&remember_me=true",
+		encode_uri(&username),
+		encode_uri(&password)
+	));
+	request.send();
+
+	let cookie = request
+		.get_header("Set-Cookie")
+		.map(|set_cookie| cookies::extract_cookie_pairs(&set_cookie.read()))
+		.unwrap_or_default();
+	if cookie.is_empty() {
+		debug!("login failed with status {}", request.status_code());
+	}
+	set_session_cookie(&cookie);
+}
+
+pub fn create_login_required_error() -> AidokuError {
+	debug!("login is required, enter credentials in the source settings");
+	// the reason only reaches the source log, see `helpers::create_adult_content_error`
+	AidokuError {
+		reason: AidokuErrorKind::DefaultNotFound,
+	}
+}
+
+// `None` when the session has expired, which redirects to the login form
+fn get_session_html(url: &str, cookie: &str) -> Result<Option<WNode>> {
+	let html = Request::new(url, HttpMethod::Get)
+		.header("Cookie", cookie)
+		.html()
+		.map(WNode::from_node)?;
+	Ok(Some(html).filter(|html| html.select("form[action*=authenticate]").is_empty()))
+}
+
+pub fn get_authorized_html(url: &str) -> Result<WNode> {
+	if let Some(cookie) = get_session_cookie() {
+		if let Some(html) = get_session_html(url, &cookie)? {
+			return Ok(html);
+		}
+	}
+
+	// the session is missing or expired, so log in once with the stored credentials and retry
+	login();
+	let cookie = get_session_cookie().ok_or_else(create_login_required_error)?;
+	get_session_html(url, &cookie)?.ok_or_else(|| {
+		set_session_cookie("");
+		create_login_required_error()
+	})
+}
@@ -0,0 +1,33 @@
+use aidoku::{
+	prelude::*,
+	std::{current_date, String},
+};
+use alloc::string::ToString;
+use common::dates;
+
+use crate::wrappers::WNode;
+
+// The date cell carries the exact time in `data-date-raw`, while its text is either
+// `dd.MM.yy`, a date with the month name or a relative phrase; error holds the reason the date
+// was not parsed
+pub fn parse_chapter_date(date_elem: Option<&WNode>) -> Result<f64, String> {
+	let date_elem = date_elem.ok_or_else(|| "no date cell in the row".to_string())?;
+
+	if let Some(raw_date) = date_elem.attr("data-date-raw") {
+		if let Some(date) = dates::parse_numeric_date(&raw_date, "yyyy-MM-dd HH:mm:ss.SSS") {
+			return Ok(date);
+		}
+	}
+
+	let visible_date = date_elem.text();
+	let visible_date = visible_date.trim();
+	if visible_date.is_empty() {
+		return Err("date cell is empty".to_string());
+	}
+
+	dates::parse_numeric_date(visible_date, "dd.MM.yy")
+		.or_else(|| dates::parse_numeric_date(visible_date, "dd.MM.yyyy"))
+		.or_else(|| dates::parse_month_name_date(visible_date, current_date()))
+		.or_else(|| dates::parse_relative_date(visible_date, current_date()))
+		.ok_or_else(|| format!("unknown date format: \"{visible_date}\""))
+}
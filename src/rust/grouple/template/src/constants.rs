@@ -0,0 +1,16 @@
+pub const SEARCH_OFFSET_STEP: i32 = 50;
+pub const BOOKMARKS_LISTING: &str = "Мои закладки";
+pub const RANDOM_LISTING: &str = "Случайная манга";
+// redirects to a random title page
+pub const RANDOM_MANGA_PATH: &str = "internal/random";
+// every title of the random listing is a separate request, so pages are kept small
+pub const RANDOM_MANGA_MAX_COUNT: usize = 20;
+// requests allowed on top of the page size for repeated, hidden or failed titles
+pub const RANDOM_MANGA_EXTRA_ATTEMPTS: usize = 5;
+// titles remembered to avoid repeats, the oldest ones are forgotten first
+pub const RANDOM_MANGA_SEEN_LIMIT: usize = 200;
+// skips the 18+ interstitial of title and chapter pages
+pub const ADULT_CONFIRMATION_PARAM: &str = "mtr=true";
+// marks ids of announced chapters, which have no pages yet
+pub const UPCOMING_CHAPTER_SUFFIX: &str = "#upcoming";
+pub const UPCOMING_CHAPTER_TEXT: &str = "Глава анонсирована, но ещё не загружена на сайт";
@@ -0,0 +1,378 @@
+use crate::{
+	constants::{
+		ADULT_CONFIRMATION_PARAM, RANDOM_MANGA_MAX_COUNT, RANDOM_MANGA_PATH, SEARCH_OFFSET_STEP,
+	},
+	wrappers::{debug, WNode},
+};
+use aidoku::std::defaults::defaults_get;
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind, NodeError, Result, ValueCastError},
+	helpers::uri::encode_uri,
+	prelude::*,
+	std::{
+		net::{HttpMethod, Request},
+		ValueRef,
+	},
+	Filter, Manga, MangaContentRating, MangaPageResult,
+};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use common::{cookies, grouple_reader, grouple_search_form, search_form};
+
+// cookie the site sets once its 18+ confirmation link is followed, kept for the session
+static mut ADULT_CONFIRMATION_COOKIE: String = String::new();
+// labels and ids of the advanced search form options, loaded once per session
+static mut FILTER_IDS: Option<Vec<(String, String)>> = None;
+// image hosts checked during the session, with whether they answered
+static mut IMAGE_HOSTS_STATUS: Vec<(String, bool)> = Vec::new();
+
+pub fn get_base_url() -> String {
+	defaults_get("baseUrl")
+		.and_then(|x| x.as_string())
+		.unwrap_or_default()
+		.to_string()
+		.trim()
+		.trim_end_matches('/')
+		.to_string()
+}
+
+pub fn get_base_search_url() -> String {
+	format!("{}/{}", get_base_url(), "search/advancedResults?")
+}
+
+fn get_advanced_search_url() -> String {
+	format!("{}/search/advanced", get_base_url())
+}
+
+pub fn get_suggestion_url(query: &str) -> String {
+	format!(
+		"{}/search/suggestion?query={}",
+		get_base_url(),
+		encode_uri(query)
+	)
+}
+
+pub fn get_random_manga_url() -> String {
+	format!("{}/{RANDOM_MANGA_PATH}", get_base_url())
+}
+
+pub fn get_random_manga_count() -> usize {
+	defaults_get("randomMangaCount")
+		.and_then(|x| x.as_string())
+		.ok()
+		.and_then(|x| x.read().parse().ok())
+		.unwrap_or(10)
+		.clamp(1, RANDOM_MANGA_MAX_COUNT)
+}
+
+pub fn show_nsfw() -> bool {
+	defaults_get("showNsfw")
+		.and_then(|x| x.as_bool())
+		.unwrap_or(true)
+}
+
+pub fn hide_upcoming_chapters() -> bool {
+	defaults_get("hideUpcomingChapters")
+		.and_then(|x| x.as_bool())
+		.unwrap_or_default()
+}
+
+pub fn auto_confirm_adult() -> bool {
+	defaults_get("autoConfirmAdult")
+		.and_then(|x| x.as_bool())
+		.unwrap_or(true)
+}
+
+fn create_get_request(url: &str) -> Request {
+	Request::new(url, HttpMethod::Get).header("Referer", "https://www.google.com/")
+}
+
+pub fn get_html(url: &str) -> Result<WNode> {
+	create_get_request(url).html().map(WNode::from_node)
+}
+
+pub fn get_json(url: &str) -> Result<ValueRef> {
+	create_get_request(url)
+		.header("X-Requested-With", "XMLHttpRequest")
+		.json()
+}
+
+fn with_adult_confirmation_param(url: &str) -> String {
+	if url.contains(ADULT_CONFIRMATION_PARAM) {
+		return url.to_string();
+	}
+	let separator = if url.contains('?') { '&' } else { '?' };
+	format!("{url}{separator}{ADULT_CONFIRMATION_PARAM}")
+}
+
+fn is_adult_gate(html: &WNode) -> bool {
+	if !html.select("div.mtr-message").is_empty() {
+		return true;
+	}
+	// the interstitial replaces the page content with the confirmation link only
+	html.select("div.leftContent, div.reader-controller")
+		.is_empty()
+		&& !html.select("a[href*='mtr=']").is_empty()
+}
+
+fn get_html_with_cookie(url: &str, cookie: &str) -> Result<WNode> {
+	let request = create_get_request(url);
+	let request = if cookie.is_empty() {
+		request
+	} else {
+		request.header("Cookie", cookie)
+	};
+	request.html().map(WNode::from_node)
+}
+
+fn resolve_link(page_url: &str, href: &str) -> Option<String> {
+	if href.starts_with("http") {
+		Some(href.to_string())
+	} else if href.starts_with("//") {
+		Some(format!("https:{href}"))
+	} else if href.starts_with('/') {
+		Some(format!("{}{href}", get_base_url()))
+	} else if href.starts_with('?') {
+		let page_url = page_url.split(['?', '#']).next().unwrap_or_default();
+		Some(format!("{page_url}{href}"))
+	} else {
+		None
+	}
+}
+
+// Follows the interstitial's own confirmation link and returns the cookie the site sets for it,
+// so its name and value are never guessed
+fn request_adult_confirmation_cookie(gate_url: &str, html: &WNode) -> Option<String> {
+	let href = html
+		.select("a[href*='mtr=']")
+		.into_iter()
+		.find_map(|link_node| link_node.attr("href"))?;
+	let request = create_get_request(&resolve_link(gate_url, &href)?);
+	request.send();
+	request
+		.get_header("Set-Cookie")
+		.map(|set_cookie| cookies::extract_cookie_pairs(&set_cookie.read()))
+		.filter(|cookie| !cookie.is_empty())
+}
+
+// Fetches title and chapter pages, which may be hidden behind the 18+ interstitial
+pub fn get_adult_gated_html(url: &str) -> Result<WNode> {
+	if !auto_confirm_adult() {
+		let html = get_html(url)?;
+		if is_adult_gate(&html) {
+			return Err(create_adult_content_error());
+		}
+		return Ok(html);
+	}
+
+	// sources are called from a single thread
+	let cookie = unsafe { &mut *core::ptr::addr_of_mut!(ADULT_CONFIRMATION_COOKIE) };
+	let url = with_adult_confirmation_param(url);
+	let mut html = get_html_with_cookie(&url, cookie)?;
+	if is_adult_gate(&html) {
+		// some pages ignore the parameter and only open with the confirmation cookie
+		if let Some(confirmation_cookie) = request_adult_confirmation_cookie(&url, &html) {
+			*cookie = confirmation_cookie;
+			html = get_html_with_cookie(&url, cookie)?;
+		}
+	}
+
+	if is_adult_gate(&html) {
+		return Err(create_adult_content_error());
+	}
+	Ok(html)
+}
+
+fn load_filter_ids() -> Option<&'static [(String, String)]> {
+	// sources are called from a single thread
+	let filter_ids = unsafe { &mut *core::ptr::addr_of_mut!(FILTER_IDS) };
+	if filter_ids.is_none() {
+		let html = get_html(&get_advanced_search_url()).ok()?;
+		*filter_ids = Some(grouple_search_form::parse_filter_ids(&html))
+			.filter(|filter_ids| !filter_ids.is_empty());
+	}
+	filter_ids.as_deref()
+}
+
+// Id of a genre or category filter as the site's search form names it, the id from the filter
+// list is only used while the form can't be loaded
+pub fn get_filter_id(filter: &Filter) -> Option<String> {
+	let listed_id = filter.object.get("id").as_string().ok().map(|id| id.read());
+	if listed_id
+		.as_deref()
+		.is_some_and(|id| !grouple_search_form::is_element_id(id))
+	{
+		return listed_id;
+	}
+
+	let Some(filter_ids) = load_filter_ids() else {
+		debug!("failed to load the advanced search form");
+		return listed_id;
+	};
+	let id = search_form::find_by_label(filter_ids, &filter.name);
+	if id.is_none() {
+		debug!("filter \"{}\" is not offered by the site", filter.name);
+	}
+	id.map(ToString::to_string)
+}
+
+pub fn get_bookmarks_url(page: i32) -> String {
+	format!(
+		"{}/private/bookmarks?offset={}",
+		get_base_url(),
+		(page - 1) * SEARCH_OFFSET_STEP
+	)
+}
+
+pub fn get_manga_url(id: &str) -> String {
+	format!("{}/{}", get_base_url(), id)
+}
+
+// `has_more` has to be decided before NSFW filtering, otherwise a single hidden title would
+// end the pagination
+pub fn create_manga_page_result(mangas: Vec<Manga>, has_more: Option<bool>) -> MangaPageResult {
+	let has_more = has_more.unwrap_or(mangas.len() == SEARCH_OFFSET_STEP as usize);
+	MangaPageResult {
+		manga: mangas
+			.into_iter()
+			.filter(|manga| show_nsfw() || manga.nsfw != MangaContentRating::Nsfw)
+			.collect(),
+		has_more,
+	}
+}
+
+pub fn get_preferred_translator() -> Option<String> {
+	defaults_get("preferredTranslator")
+		.and_then(|x| x.as_string())
+		.map(|x| x.read().trim().to_lowercase())
+		.ok()
+		.filter(|x| !x.is_empty())
+}
+
+fn get_hosts_setting(key: &str) -> Vec<String> {
+	defaults_get(key)
+		.and_then(|x| x.as_string())
+		.map(|x| {
+			x.read()
+				.split([',', ' '])
+				.map(|host| host.trim().to_lowercase())
+				.filter(|host| !host.is_empty())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Returns preferred and excluded image hosts in that order
+pub fn get_image_hosts_preferences() -> (Vec<String>, Vec<String>) {
+	(
+		get_hosts_setting("preferredImageHosts"),
+		get_hosts_setting("excludedImageHosts"),
+	)
+}
+
+fn is_image_host_available(url: &str) -> bool {
+	// sources are called from a single thread
+	let hosts_status = unsafe { &mut *core::ptr::addr_of_mut!(IMAGE_HOSTS_STATUS) };
+	let host = grouple_reader::get_url_host(url).to_lowercase();
+	if let Some((_, is_available)) = hosts_status
+		.iter()
+		.find(|(known_host, _)| *known_host == host)
+	{
+		return *is_available;
+	}
+
+	let request = Request::new(url, HttpMethod::Head).header("Referer", &get_base_url());
+	request.send();
+	// servers without HEAD support answer 405, which still means they are up
+	let is_available = matches!(request.status_code(), 200..=399 | 405);
+	if !is_available {
+		debug!("image server {host} is not available");
+	}
+	hosts_status.push((host, is_available));
+	is_available
+}
+
+// First url of the page whose server answers, each server is checked once per session
+pub fn pick_image_url(url: &str) -> String {
+	let (url, fallback_urls) = grouple_reader::split_fallback_urls(url);
+	if fallback_urls.is_empty() {
+		return url;
+	}
+	core::iter::once(&url)
+		.chain(&fallback_urls)
+		.find(|url| is_image_host_available(url))
+		.unwrap_or(&url)
+		.clone()
+}
+
+pub fn get_chapter_url(manga_id: &str, chapter_id: &str) -> String {
+	let url = format!("{}/{}/{}", get_base_url(), manga_id, chapter_id);
+	if auto_confirm_adult() {
+		// mtr is 18+ skip, chapter id may already carry the translator parameter
+		with_adult_confirmation_param(&url)
+	} else {
+		url
+	}
+}
+
+fn content_rating_level(rating: &MangaContentRating) -> u8 {
+	match rating {
+		MangaContentRating::Safe => 0,
+		MangaContentRating::Suggestive => 1,
+		MangaContentRating::Nsfw => 2,
+	}
+}
+
+pub fn parse_content_rating(
+	markers: &[String],
+	is_adult: bool,
+	min_rating: &MangaContentRating,
+) -> MangaContentRating {
+	// markers are free-form texts like "NC-17" or "Возрастная рекомендация: R"
+	let has_rating = |rating: &str| {
+		markers.iter().any(|marker| {
+			marker
+				.split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '+'))
+				.any(|token| token.eq_ignore_ascii_case(rating))
+		})
+	};
+
+	let rating = if is_adult || has_rating("NC-17") || has_rating("18+") {
+		MangaContentRating::Nsfw
+	} else if has_rating("R") || has_rating("16+") {
+		MangaContentRating::Suggestive
+	} else {
+		MangaContentRating::Safe
+	};
+
+	// sites dedicated to mature titles don't mark every one of them
+	match content_rating_level(&rating).max(content_rating_level(min_rating)) {
+		0 => MangaContentRating::Safe,
+		1 => MangaContentRating::Suggestive,
+		_ => MangaContentRating::Nsfw,
+	}
+}
+
+pub fn create_parsing_error() -> AidokuError {
+	AidokuError {
+		reason: AidokuErrorKind::NodeError(NodeError::ParseError),
+	}
+}
+
+// AidokuError carries nothing but its kind and the app shows the same generic failure for any
+// of them, so the reason of the errors below only reaches the source log
+pub fn create_adult_content_error() -> AidokuError {
+	debug!("adult content: confirmation is required, enable it in the source settings");
+	AidokuError {
+		reason: AidokuErrorKind::ValueCast(ValueCastError::NotNode),
+	}
+}
+
+pub fn create_excluded_image_hosts_error() -> AidokuError {
+	debug!("every image server of the page is excluded in the source settings");
+	AidokuError {
+		reason: AidokuErrorKind::ValueCast(ValueCastError::NotString),
+	}
+}
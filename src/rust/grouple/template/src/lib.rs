@@ -0,0 +1,14 @@
+#![no_std]
+#![feature(pattern)]
+#![feature(iter_intersperse)]
+
+mod auth;
+mod chapter_date;
+mod constants;
+mod helpers;
+mod parser;
+mod sorting;
+pub mod template;
+mod wrappers;
+
+extern crate alloc;
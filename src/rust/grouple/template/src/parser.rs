@@ -0,0 +1,705 @@
+use aidoku::{
+	error::{AidokuError, AidokuErrorKind, Result},
+	helpers::uri::encode_uri,
+	prelude::*,
+	std::{String, ValueRef, Vec},
+	Chapter, Filter, FilterType, Manga, MangaStatus, MangaViewer, Page,
+};
+
+extern crate alloc;
+use alloc::{string::ToString, vec};
+
+use common::{
+	chapter_label, grouple_reader,
+	grouple_tile::{self, TileStatus},
+};
+use itertools::chain;
+
+use crate::{
+	chapter_date,
+	constants::{SEARCH_OFFSET_STEP, UPCOMING_CHAPTER_SUFFIX},
+	helpers,
+	sorting::Sorting,
+	template::GroupleSiteData,
+	wrappers::{debug, WNode},
+};
+
+fn parse_manga_tile(node: &WNode, data: &GroupleSiteData) -> Option<Manga> {
+	let tile = grouple_tile::parse_tile(node)?;
+
+	let nsfw =
+		helpers::parse_content_rating(&tile.rating_markers, tile.is_adult, &data.content_rating);
+	let status = match tile.status {
+		TileStatus::Ongoing => MangaStatus::Ongoing,
+		TileStatus::Completed => MangaStatus::Completed,
+		TileStatus::Unknown => MangaStatus::Unknown,
+	};
+
+	Some(Manga {
+		url: helpers::get_manga_url(&tile.id),
+		id: tile.id,
+		cover: tile.cover,
+		title: tile.title,
+		author: tile.authors.join(", "),
+		artist: "".to_string(),
+		description: tile.description,
+		categories: tile.categories,
+		status,
+		nsfw,
+		viewer: MangaViewer::Rtl,
+	})
+}
+
+pub fn parse_search_results(html: &WNode, data: &GroupleSiteData) -> Result<Vec<Manga>> {
+	let mangas: Vec<_> = html
+		.select("div.tile")
+		.iter()
+		.filter_map(|node| parse_manga_tile(node, data))
+		.collect();
+
+	Ok(mangas)
+}
+
+pub fn parse_bookmarks(html: &WNode, data: &GroupleSiteData) -> Result<Vec<Manga>> {
+	let mangas: Vec<_> = html
+		.select("div.tile")
+		.iter()
+		.filter_map(|node| {
+			let mut manga = parse_manga_tile(node, data)?;
+			if let Some(bookmark_status) = grouple_tile::parse_bookmark_status(node) {
+				manga
+					.categories
+					.insert(0, format!("Закладка: {bookmark_status}"));
+			}
+			Some(manga)
+		})
+		.collect();
+
+	Ok(mangas)
+}
+
+pub fn parse_has_more(html: &WNode) -> Option<bool> {
+	if !html.select("a.nextLink").is_empty() {
+		return Some(true);
+	}
+	// the control is rendered without the next link on the last page
+	if !html.select(".pagination").is_empty() {
+		return Some(false);
+	}
+	None
+}
+
+fn get_manga_page_main_node(html: &WNode) -> Result<WNode> {
+	html.select("div.leftContent")
+		.pop()
+		.ok_or(helpers::create_parsing_error())
+}
+
+pub fn parse_manga(html: &WNode, id: String, data: &GroupleSiteData) -> Result<Manga> {
+	let parsing_error = helpers::create_parsing_error();
+
+	let main_node = get_manga_page_main_node(html)?;
+
+	let main_attributes_node = main_node
+		.select("div.flex-row")
+		.pop()
+		.ok_or(parsing_error)?;
+
+	let picture_fotorama_node = main_attributes_node.select("div.picture-fotorama").pop();
+	let cover = picture_fotorama_node
+		.and_then(|pfn| {
+			let imgs = pfn.select("img");
+			imgs.into_iter().next()
+		})
+		.and_then(|img_node| {
+			img_node
+				.attr("data-full")
+				.or_else(|| img_node.attr("data-thumb"))
+				.or_else(|| img_node.attr("src"))
+		})
+		.map(|url| {
+			if url.contains("://") {
+				url
+			} else {
+				format!("https:{url}")
+			}
+		})
+		.unwrap_or_default();
+
+	let names_node = main_node.select("h1.names").pop().ok_or(parsing_error)?;
+	let title = names_node
+		.select("span.name")
+		.pop()
+		.ok_or(parsing_error)?
+		.text();
+
+	let main_info_node = main_attributes_node
+		.select("div.subject-meta")
+		.pop()
+		.ok_or(parsing_error)?;
+
+	let extract_info_iter = |elem_class, link_type| {
+		main_info_node
+			.select(&format!("span.elem_{elem_class}"))
+			.into_iter()
+			.flat_map(move |node| {
+				node.select(&format!("a.{link_type}-link"))
+					.into_iter()
+					.map(|person_node| person_node.text())
+			})
+	};
+
+	let author = chain!(
+		extract_info_iter("author", "person"),
+		extract_info_iter("screenwriter", "person")
+	)
+	.intersperse(", ".to_string())
+	.collect();
+
+	let artist = extract_info_iter("illustrator", "person")
+		.intersperse(", ".to_string())
+		.collect();
+
+	let synopsis = main_node
+		.select("div.manga-description")
+		.pop()
+		.map(|desc_node| {
+			desc_node.text_with_newlines(".spoiler, [class*=read-more], [class*=more-link]")
+		})
+		.filter(|desc| !desc.is_empty())
+		.or_else(|| {
+			main_node
+				.select("meta")
+				.into_iter()
+				.find(|mn| {
+					if let Some(itemprop) = mn.attr("itemprop") {
+						return itemprop == "description";
+					}
+					false
+				})
+				.and_then(|desc_node| desc_node.attr("content"))
+		})
+		.unwrap_or_default();
+
+	let description = {
+		let extract_name = |class_name| {
+			names_node
+				.select(&format!("span.{class_name}"))
+				.pop()
+				.map(|name_node| name_node.text().trim().to_string())
+				.filter(|name| !name.is_empty() && name != &title)
+		};
+		let year = extract_info_iter("year", "element").next();
+		let score = main_node
+			.select("span.rating-block")
+			.pop()
+			.and_then(|rating_node| rating_node.attr("data-score"))
+			.or_else(|| {
+				main_node
+					.select("meta[itemprop=ratingValue]")
+					.pop()
+					.and_then(|rating_node| rating_node.attr("content"))
+			});
+		let translators: String = extract_info_iter("translator", "person")
+			.intersperse(", ".to_string())
+			.collect();
+
+		let header: Vec<String> = [
+			("Англ. название", extract_name("eng-name")),
+			("Оригинальное название", extract_name("original-name")),
+			("Год выпуска", year),
+			("Оценка", score),
+			(
+				"Переводчики",
+				Some(translators).filter(|translators| !translators.is_empty()),
+			),
+		]
+		.into_iter()
+		.filter_map(|(name, value)| Some(format!("{name}: {}", value?.trim())))
+		.collect();
+
+		if header.is_empty() {
+			synopsis
+		} else {
+			format!("{}\n\n{synopsis}", header.join("\n"))
+				.trim_end()
+				.to_string()
+		}
+	};
+
+	let url = helpers::get_manga_url(&id);
+
+	let category_opt = extract_info_iter("category", "element").next();
+
+	let viewer = match &category_opt {
+		Some(category) => match category.to_lowercase().as_str() {
+			"oel-манга" => MangaViewer::Scroll,
+			"комикс" => MangaViewer::Ltr,
+			"манхва" => MangaViewer::Scroll,
+			"маньхуа" => MangaViewer::Scroll,
+			_ => MangaViewer::default(),
+		},
+		None => MangaViewer::default(),
+	};
+
+	let mut categories: Vec<String> = Vec::new();
+	if let Some(category) = category_opt {
+		categories.push(category);
+	}
+	// Genres
+	categories.extend(
+		main_info_node
+			.select("a.elem_genre")
+			.iter()
+			.map(WNode::text),
+	);
+	categories.extend(
+		main_info_node
+			.select("span.elem_genre")
+			.iter()
+			.map(WNode::text),
+	);
+	// Tags
+	categories.extend(main_info_node.select("a.elem_tag").iter().map(WNode::text));
+	categories.extend(
+		main_info_node
+			.select("span.elem_tag")
+			.iter()
+			.map(WNode::text),
+	);
+
+	let badge_texts: Vec<String> = main_info_node
+		.select("p span.badge")
+		.iter()
+		.map(WNode::text)
+		.map(|s| s.to_lowercase())
+		.collect();
+	let nsfw = {
+		let mut rating_markers: Vec<String> = categories.clone();
+		rating_markers.extend(badge_texts.iter().cloned());
+		rating_markers.extend(
+			main_info_node
+				.select("span.elem_limitation")
+				.iter()
+				.map(WNode::text),
+		);
+		let is_adult = !main_node.select("[class*=mature]").is_empty()
+			|| badge_texts.iter().any(|t| t.contains("для взрослых"));
+		helpers::parse_content_rating(&rating_markers, is_adult, &data.content_rating)
+	};
+
+	let status = if badge_texts.iter().any(|t| {
+		t.contains("выпуск завершён") || t.contains("завершён") || t.contains("переведено")
+	}) {
+		MangaStatus::Completed
+	} else if badge_texts
+		.iter()
+		.any(|t| t.contains("выпуск продолжается") || t.contains("переводится"))
+	{
+		MangaStatus::Ongoing
+	} else if badge_texts.iter().any(|t| {
+		t.contains("перевод приостановлен")
+			|| t.contains("выпуск остановлен")
+			|| t.contains("заморожен")
+	}) {
+		MangaStatus::Hiatus
+	} else {
+		MangaStatus::Unknown
+	};
+
+	Ok(Manga {
+		id,
+		cover,
+		title,
+		author,
+		artist,
+		description,
+		url,
+		categories,
+		status,
+		nsfw,
+		viewer,
+	})
+}
+
+pub fn parse_chapters(html: &WNode, manga_id: &str) -> Result<Vec<Chapter>> {
+	let main_node = get_manga_page_main_node(html)?;
+	let preferred_translator = helpers::get_preferred_translator();
+	let hide_upcoming = helpers::hide_upcoming_chapters();
+
+	let chapter_elems = main_node.select("div[class~=chapters] > table > tbody > tr:has(td > a)");
+	// rows go from the newest chapter to the oldest one
+	let chapters_count = chapter_elems.len();
+
+	let rows = chapter_elems
+		.into_iter()
+		.enumerate()
+		.filter_map(|(index, chapter_elem)| {
+			// announced chapters that are not uploaded yet
+			let is_upcoming = !chapter_elem.select("td.date.text-info").is_empty();
+			if is_upcoming && hide_upcoming {
+				return None;
+			}
+
+			let link_elem = chapter_elem
+				.select("a.chapter-link")
+				.pop()
+				.or_else(|| chapter_elem.select("td > a").into_iter().next())?;
+
+			let strip_manga_prefix = |url: String| {
+				let path = match url.split_once("://") {
+					Some((_, rest)) => rest.find('/').map_or("", |path_start| &rest[path_start..]),
+					None => &url,
+				};
+				path.strip_prefix(format!("/{manga_id}/").as_str())
+					.map(ToString::to_string)
+			};
+
+			let base_id = strip_manga_prefix(link_elem.attr("href")?)?;
+
+			let full_title = link_elem.text().replace(" новое", "").trim().to_string();
+			let id_number = chapter_label::parse_chapter_path(&base_id);
+			let title_number = chapter_label::parse_chapter_label(&full_title);
+			let is_extra = chapter_label::is_extra_chapter(&id_number, &title_number);
+
+			let volume = id_number.volume.or(title_number.volume).unwrap_or(-1.0);
+			let chapter = id_number
+				.chapter
+				.or(title_number.chapter)
+				.unwrap_or((chapters_count - index) as f32);
+
+			let title = {
+				let strippred_title: String = full_title
+					.chars()
+					.skip_while(|char| char.is_numeric() || char.is_whitespace() || char == &'-')
+					.collect();
+				let title = if strippred_title.is_empty() {
+					full_title
+				} else {
+					strippred_title
+				};
+				if is_extra && !title_number.is_extra {
+					format!("{title} (экстра)")
+				} else {
+					title
+				}
+			};
+
+			let date_updated =
+				chapter_date::parse_chapter_date(chapter_elem.select("td.date").first())
+					.unwrap_or_else(|reason| {
+						debug!("failed to parse date of chapter {base_id}: {reason}");
+						0f64
+					});
+
+			if is_upcoming {
+				return Some((
+					is_extra,
+					vec![Chapter {
+						id: format!("{base_id}{UPCOMING_CHAPTER_SUFFIX}"),
+						title: format!("{title} 🔒 (ожидает загрузки)"),
+						volume,
+						chapter,
+						date_updated,
+						url: helpers::get_chapter_url(manga_id, &base_id),
+						lang: "ru".to_string(),
+						..Default::default()
+					}],
+				));
+			}
+
+			let clean_scanlator = |raw: String| raw.replace(" (Переводчик)", "").trim().to_string();
+
+			// Every translation besides the default one is a link to the same chapter with the
+			// translator picked via `tran` parameter
+			let mut translations = vec![(
+				base_id.clone(),
+				clean_scanlator(link_elem.attr("title").unwrap_or_default()),
+			)];
+			for translation_elem in chapter_elem.select("a[href*='tran=']") {
+				let Some(id) = translation_elem.attr("href").and_then(strip_manga_prefix) else {
+					continue;
+				};
+				if translations.iter().any(|(known_id, _)| known_id == &id) {
+					continue;
+				}
+				let scanlator = clean_scanlator(
+					translation_elem
+						.attr("title")
+						.unwrap_or_else(|| translation_elem.text()),
+				);
+				translations.push((id, scanlator));
+			}
+
+			// every translation stays available, the preferred one is just listed first
+			if let Some(preferred) = &preferred_translator {
+				translations.sort_by_key(|(_, scanlator)| scanlator.to_lowercase() != *preferred);
+			}
+
+			Some((
+				is_extra,
+				translations
+					.into_iter()
+					.map(|(id, scanlator)| {
+						let url = helpers::get_chapter_url(manga_id, &id);
+						Chapter {
+							id,
+							title: title.clone(),
+							volume,
+							chapter,
+							date_updated,
+							scanlator,
+							url,
+							lang: "ru".to_string(),
+						}
+					})
+					.collect::<Vec<_>>(),
+			))
+		})
+		.collect::<Vec<_>>();
+
+	// rows keep the number of every translation the same
+	let numbers = chapter_label::number_extras(
+		&rows
+			.iter()
+			.map(|(is_extra, translations)| {
+				(
+					translations.first().map_or(-1.0, |chapter| chapter.chapter),
+					*is_extra,
+				)
+			})
+			.collect::<Vec<_>>(),
+	);
+	let chapters = rows
+		.into_iter()
+		.zip(numbers)
+		.flat_map(|((_, translations), number)| {
+			translations.into_iter().map(move |chapter| Chapter {
+				chapter: number,
+				..chapter
+			})
+		})
+		.collect();
+
+	Ok(chapters)
+}
+
+pub fn get_page_list(html: &WNode) -> Result<Vec<Page>> {
+	let parsing_error = helpers::create_parsing_error();
+
+	let reader_data = grouple_reader::parse_reader_page(html).ok_or(parsing_error)?;
+
+	let (preferred_hosts, excluded_hosts) = helpers::get_image_hosts_preferences();
+
+	reader_data
+		.pages
+		.iter()
+		.enumerate()
+		.map(|(idx, page)| {
+			let urls: Vec<String> = match page.server_and_path() {
+				("", path) => vec![format!("{}{path}", helpers::get_base_url())],
+				(server, path) => grouple_reader::get_page_servers(
+					server,
+					&reader_data.servers,
+					&preferred_hosts,
+					&excluded_hosts,
+				)
+				.into_iter()
+				.map(|server| format!("{server}{path}"))
+				.collect(),
+			}
+			.into_iter()
+			.map(grouple_reader::normalize_image_url)
+			.filter(|url| !grouple_reader::matches_any_host(url, &excluded_hosts))
+			.collect();
+
+			// the other servers are tried by `modify_image_request` when the first one is down
+			let (url, fallback_urls) = urls
+				.split_first()
+				.ok_or_else(helpers::create_excluded_image_hosts_error)?;
+			Ok(Page {
+				index: idx as i32,
+				url: grouple_reader::with_fallback_urls(url.clone(), fallback_urls),
+				..Default::default()
+			})
+		})
+		.collect()
+}
+
+pub fn get_filter_url(filters: &[Filter], sorting: &Sorting, page: i32) -> Result<String> {
+	let mut params: Vec<String> = Vec::new();
+
+	params.push(format!("offset={}", (page - 1) * SEARCH_OFFSET_STEP));
+	params.push(format!("sortType={}", sorting));
+
+	for filter in filters {
+		match filter.kind {
+			FilterType::Title => {
+				if let Ok(title_ref) = filter.value.clone().as_string() {
+					params.push(format!("q={}", encode_uri(title_ref.read())));
+				}
+			}
+			FilterType::Genre => {
+				let value = match filter.value.as_int().unwrap_or(-1) {
+					0 => "out", // excluded
+					1 => "in",  // included
+					_ => continue,
+				};
+				if let Some(id) = helpers::get_filter_id(filter) {
+					params.push(format!("{}={}", id, value));
+				}
+			}
+			FilterType::Check => {
+				// Any checked option => add `=in`
+				if filter.value.as_int().unwrap_or(0) == 0 {
+					continue;
+				}
+				if let Some(id) = helpers::get_filter_id(filter) {
+					params.push(format!("{}=in", id));
+				}
+			}
+			_ => {}
+		}
+	}
+
+	params.sort_by(|a, b| {
+		let a_is_q = a.starts_with("q=");
+		let b_is_q = b.starts_with("q=");
+		b_is_q.cmp(&a_is_q)
+	});
+
+	Ok(format!(
+		"{}{}",
+		helpers::get_base_search_url(),
+		params.join("&")
+	))
+}
+
+// Title pages don't expose their id directly, so it is taken from the links pointing to the
+// page itself
+pub fn parse_manga_id(html: &WNode) -> Option<String> {
+	let page_url = html
+		.select("meta[property=og:url]")
+		.pop()
+		.and_then(|meta_node| meta_node.attr("content"))
+		.or_else(|| {
+			html.select("link[rel=canonical]")
+				.pop()
+				.and_then(|link_node| link_node.attr("href"))
+		});
+	if let Some(page_url) = page_url {
+		return parse_incoming_url(&page_url)
+			.ok()
+			.map(|(manga_id, _)| manga_id);
+	}
+
+	// chapter links are /<manga>/vol<N>/<M>
+	html.select("a.chapter-link")
+		.into_iter()
+		.find_map(|link_node| {
+			let href = link_node.attr("href")?;
+			let manga_id = href.trim_start_matches('/').split('/').next()?;
+			Some(manga_id.to_string()).filter(|id| !id.is_empty())
+		})
+}
+
+// Returns the title query when no other filter narrows the search
+pub fn get_title_only_query(filters: &[Filter]) -> Option<String> {
+	let narrows_search = |filter: &Filter| match filter.kind {
+		FilterType::Genre => matches!(filter.value.as_int().unwrap_or(-1), 0 | 1),
+		FilterType::Check => filter.value.as_int().unwrap_or(0) != 0,
+		_ => false,
+	};
+	if filters.iter().any(narrows_search) {
+		return None;
+	}
+
+	filters
+		.iter()
+		.find(|filter| matches!(filter.kind, FilterType::Title))
+		.and_then(|filter| filter.value.clone().as_string().ok())
+		.map(|title_ref| title_ref.read().trim().to_string())
+		.filter(|query| !query.is_empty())
+}
+
+// {"suggestions":[{"value":"Наруто","data":{"link":"/naruto"},"thumbnail":"https://..."}]}
+pub fn parse_suggestions(json: ValueRef, data: &GroupleSiteData) -> Result<Vec<Manga>> {
+	let suggestions = json.as_object()?.get("suggestions").as_array()?;
+
+	let mangas = suggestions
+		.filter_map(|suggestion| {
+			let suggestion = suggestion.as_object().ok()?;
+			let link = suggestion
+				.get("data")
+				.as_object()
+				.ok()?
+				.get("link")
+				.as_string()
+				.ok()?
+				.read();
+
+			// persons and other entities share the endpoint with titles
+			let id = link.trim_start_matches('/').to_string();
+			if id.is_empty() || id.contains('/') {
+				return None;
+			}
+
+			let title = suggestion.get("value").as_string().ok()?.read();
+			let cover = suggestion
+				.get("thumbnail")
+				.as_string()
+				.map(|thumbnail| thumbnail.read())
+				.unwrap_or_default();
+			let url = helpers::get_manga_url(&id);
+
+			// suggestions carry no age markers, the rating is resolved by `parse_manga` once the
+			// title is opened
+			Some(Manga {
+				id,
+				cover,
+				title,
+				url,
+				nsfw: helpers::parse_content_rating(&[], false, &data.content_rating),
+				viewer: MangaViewer::Rtl,
+				..Default::default()
+			})
+		})
+		.collect();
+
+	Ok(mangas)
+}
+
+// Returns manga id and chapter id, if the url points to a chapter
+pub fn parse_incoming_url(url: &str) -> Result<(String, Option<String>)> {
+	// https://<mirror>/<manga>[/vol<N>/<M>][?mtr=true][#page=<P>]
+	let path_segments: Vec<_> = url
+		.split_once("://")
+		.map_or(url, |(_, rest)| rest)
+		.split(['?', '#'])
+		.next()
+		.unwrap_or_default()
+		.split('/')
+		.skip(1)
+		.filter(|segment| !segment.is_empty())
+		.collect();
+
+	let manga_id = path_segments.first().ok_or(AidokuError {
+		reason: AidokuErrorKind::Unimplemented,
+	})?;
+
+	let chapter_id = match &path_segments[..] {
+		[_, vol_str, chap_str, ..] if vol_str.starts_with("vol") => {
+			// Shared links to a specific translation keep the translator in `tran` parameter
+			match url
+				.split(['?', '&', '#'])
+				.find(|param| param.starts_with("tran="))
+			{
+				Some(tran_param) => Some(format!("{vol_str}/{chap_str}?{tran_param}")),
+				None => Some(format!("{vol_str}/{chap_str}")),
+			}
+		}
+		_ => None,
+	};
+
+	Ok((manga_id.to_string(), chapter_id))
+}
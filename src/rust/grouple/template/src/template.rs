@@ -0,0 +1,174 @@
+use aidoku::{
+	error::Result,
+	std::{net::Request, String, Vec},
+	Chapter, DeepLink, Filter, Listing, Manga, MangaContentRating, MangaPageResult, Page,
+};
+use alloc::{string::ToString, vec};
+
+use crate::{auth, constants, helpers, parser, sorting::Sorting};
+
+// ids returned by the random listing since its first page was opened, so titles don't repeat
+static mut RANDOM_MANGA_IDS: Vec<String> = Vec::new();
+
+// Per-site settings of a Grouple engine source, the rest (domain, filter list) comes from the
+// source resources
+pub struct GroupleSiteData {
+	// rating given to titles without any age rating markers
+	pub content_rating: MangaContentRating,
+}
+
+pub fn get_manga_list(
+	filters: Vec<Filter>,
+	page: i32,
+	data: &GroupleSiteData,
+) -> Result<MangaPageResult> {
+	// Plain title lookups go through the suggestion endpoint first. By estimate, not measured
+	// here: a page of 50 tiles is about 150-250 KB of HTML, a suggestion list of up to ten
+	// entries is 1-3 KB of JSON. Suggestions have no age rating, so the NSFW filter can only
+	// be honored by the advanced search.
+	if page == 1 && helpers::show_nsfw() {
+		if let Some(query) = parser::get_title_only_query(&filters) {
+			let suggestions = helpers::get_json(&helpers::get_suggestion_url(&query))
+				.and_then(|json| parser::parse_suggestions(json, data));
+			if let Ok(mangas) = suggestions {
+				if !mangas.is_empty() {
+					return Ok(helpers::create_manga_page_result(mangas, Some(false)));
+				}
+			}
+		}
+	}
+
+	let search_url = parser::get_filter_url(&filters, &Sorting::default(), page)?;
+	let html = helpers::get_html(&search_url)?;
+	let mangas = parser::parse_search_results(&html, data)?;
+	let has_more = parser::parse_has_more(&html);
+	Ok(helpers::create_manga_page_result(mangas, has_more))
+}
+
+pub fn get_manga_listing(
+	listing: Listing,
+	page: i32,
+	data: &GroupleSiteData,
+) -> Result<MangaPageResult> {
+	if listing.name == constants::BOOKMARKS_LISTING {
+		let url = helpers::get_bookmarks_url(page);
+		let html = auth::get_authorized_html(&url)?;
+		let mangas = parser::parse_bookmarks(&html, data)?;
+		let has_more = parser::parse_has_more(&html);
+		return Ok(helpers::create_manga_page_result(mangas, has_more));
+	}
+
+	if listing.name == constants::RANDOM_LISTING {
+		return get_random_manga_page(page, data);
+	}
+
+	let sorting = Sorting::from_listing(&listing);
+	let url = parser::get_filter_url(&[], &sorting, page)?;
+	let html = helpers::get_html(&url)?;
+	let mangas = parser::parse_search_results(&html, data)?;
+	let has_more = parser::parse_has_more(&html);
+	Ok(helpers::create_manga_page_result(mangas, has_more))
+}
+
+fn get_random_manga_page(page: i32, data: &GroupleSiteData) -> Result<MangaPageResult> {
+	// sources are called from a single thread
+	let seen_ids = unsafe { &mut *core::ptr::addr_of_mut!(RANDOM_MANGA_IDS) };
+	// reopening the listing starts it over
+	if page == 1 {
+		seen_ids.clear();
+	}
+
+	let count = helpers::get_random_manga_count();
+	let max_attempts = count + constants::RANDOM_MANGA_EXTRA_ATTEMPTS;
+
+	let mut mangas = Vec::new();
+	for _ in 0..max_attempts {
+		if mangas.len() >= count {
+			break;
+		}
+		let Ok(html) = helpers::get_adult_gated_html(&helpers::get_random_manga_url()) else {
+			continue;
+		};
+		let Some(manga_id) = parser::parse_manga_id(&html) else {
+			continue;
+		};
+		if seen_ids.contains(&manga_id) {
+			continue;
+		}
+		if seen_ids.len() >= constants::RANDOM_MANGA_SEEN_LIMIT {
+			seen_ids.remove(0);
+		}
+		seen_ids.push(manga_id.clone());
+
+		let Ok(manga) = parser::parse_manga(&html, manga_id, data) else {
+			continue;
+		};
+		if !helpers::show_nsfw() && manga.nsfw == MangaContentRating::Nsfw {
+			continue;
+		}
+		mangas.push(manga);
+	}
+
+	let has_more = !mangas.is_empty();
+	Ok(helpers::create_manga_page_result(mangas, Some(has_more)))
+}
+
+pub fn get_manga_details(manga_id: String, data: &GroupleSiteData) -> Result<Manga> {
+	let url = helpers::get_manga_url(&manga_id);
+	let html = helpers::get_adult_gated_html(&url)?;
+	parser::parse_manga(&html, manga_id, data)
+}
+
+pub fn get_chapter_list(manga_id: String) -> Result<Vec<Chapter>> {
+	let url = helpers::get_manga_url(&manga_id);
+	let html = helpers::get_adult_gated_html(&url)?;
+	parser::parse_chapters(&html, &manga_id)
+}
+
+pub fn get_page_list(manga_id: String, chapter_id: String) -> Result<Vec<Page>> {
+	// an announced chapter has no pages yet, so the reader shows why instead of failing
+	if chapter_id.ends_with(constants::UPCOMING_CHAPTER_SUFFIX) {
+		return Ok(vec![Page {
+			index: 0,
+			text: constants::UPCOMING_CHAPTER_TEXT.to_string(),
+			..Default::default()
+		}]);
+	}
+	let url = helpers::get_chapter_url(&manga_id, &chapter_id);
+	let html = helpers::get_adult_gated_html(&url)?;
+	parser::get_page_list(&html)
+}
+
+pub fn modify_image_request(request: Request) {
+	// image servers refuse hotlinked requests without the site referer
+	let request = request.header("Referer", helpers::get_base_url().as_str());
+	// page urls carry the alternative servers of the image, see `parser::get_page_list`
+	let url = request.get_url().read();
+	let image_url = helpers::pick_image_url(&url);
+	if image_url != url {
+		request.url(image_url);
+	}
+}
+
+pub fn handle_notification(notification: String) {
+	if notification.as_str() == "login" {
+		auth::login();
+	}
+}
+
+pub fn handle_url(url: String, data: &GroupleSiteData) -> Result<DeepLink> {
+	let (manga_id, chapter_id) = parser::parse_incoming_url(&url)?;
+
+	let manga = get_manga_details(manga_id, data)?;
+	let chapter = match chapter_id {
+		Some(chapter_id) => get_chapter_list(manga.id.clone())?
+			.into_iter()
+			.find(|chapter| chapter.id == chapter_id),
+		None => None,
+	};
+
+	Ok(DeepLink {
+		manga: Some(manga),
+		chapter,
+	})
+}
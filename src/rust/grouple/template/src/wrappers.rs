@@ -0,0 +1,159 @@
+use aidoku::{error::Result, helpers::substring::Substring, prelude::*, std::html::Node};
+use alloc::{string::String, vec::Vec};
+use common::dom::Element;
+
+macro_rules! debug {
+	($($arg:tt)*) => {{
+		println!("grouple:: {}:{}: {}", file!(), line!(), format!($($arg)*))
+	}};
+}
+pub(crate) use debug;
+
+#[derive(Debug, Clone)]
+pub struct WNode {
+	repr: String,
+}
+
+impl WNode {
+	pub fn _new(repr: String) -> Self {
+		WNode { repr }
+	}
+
+	pub fn from_node(node: Node) -> Self {
+		let repr = node.outer_html().read();
+		// debug!("repr: \"{}\"", repr);
+		if repr.starts_with("<html>") {
+			let lines: Vec<_> = repr.lines().collect();
+			// debug!("lines: {:?}", lines);
+			WNode {
+				repr: lines[3..lines.len() - 2].join("\n"),
+			}
+		} else {
+			WNode { repr }
+		}
+	}
+
+	pub fn select(&self, selector: &str) -> Vec<WNode> {
+		// we need this due to refcount error in aidoku-rs: https://github.com/Aidoku/aidoku-rs/issues/4
+		let mut res = Vec::new();
+		let html = self.to_node();
+		for val in html.select(selector).array() {
+			let node_res = val.as_node();
+			if node_res.is_err() {
+				debug!("failed conversion to Node");
+			}
+			res.push(WNode::from_node(node_res.unwrap()));
+		}
+		res
+	}
+
+	pub fn attr(&self, attr: &str) -> Option<String> {
+		let attributes_str = self
+			.repr
+			.substring_before(">")?
+			.substring_after("<")?
+			.split_once(' ')?
+			.1;
+
+		let attr_idx = attributes_str.find(attr)?;
+		let val: String = attributes_str[attr_idx..]
+			.chars()
+			.skip_while(|c| c != &'=')
+			.skip_while(|c| c != &'"')
+			.skip(1)
+			.take_while(|c| c != &'"')
+			.collect();
+
+		if val.is_empty() {
+			None
+		} else {
+			Some(val)
+		}
+	}
+
+	pub fn text(&self) -> String {
+		self.to_node().text().read()
+	}
+
+	pub fn data(&self) -> String {
+		self.to_node().data().read()
+	}
+
+	// Text keeping line breaks and paragraphs, elements matching `removed_selector` are dropped
+	// together with their content
+	pub fn text_with_newlines(&self, removed_selector: &str) -> String {
+		// text() collapses whitespace, so breaks are marked with an escaped newline instead
+		const NEWLINE_MARKER: &str = "\\n";
+
+		let node = self.to_node();
+		let for_each_node = |selector: &str, modify: &dyn Fn(&mut Node) -> Result<()>| {
+			for value in node.select(selector).array() {
+				if let Ok(mut child) = value.as_node() {
+					if modify(&mut child).is_err() {
+						debug!("failed to modify \"{}\" element", selector);
+					}
+				}
+			}
+		};
+		for_each_node(removed_selector, &|child| child.set_html(""));
+		for_each_node("br, div, li, h3, h4", &|child| child.append(NEWLINE_MARKER));
+		// paragraphs are separated by an empty line
+		for_each_node("p", &|child| {
+			child.append(format!("{NEWLINE_MARKER}{NEWLINE_MARKER}"))
+		});
+
+		let text = node.text().read().replace(NEWLINE_MARKER, "\n");
+		let mut result = String::new();
+		let mut empty_lines = 0;
+		for line in text.lines().map(str::trim) {
+			if line.is_empty() {
+				empty_lines += 1;
+				continue;
+			}
+			if !result.is_empty() {
+				result.push_str(if empty_lines > 0 { "\n\n" } else { "\n" });
+			}
+			result.push_str(line);
+			empty_lines = 0;
+		}
+		result
+	}
+
+	fn to_node(&self) -> Node {
+		// table rows and cells are dropped by the parser unless they are inside of a table
+		let repr = if self.repr.starts_with("<tr") {
+			format!("<table><tbody>{}</tbody></table>", self.repr)
+		} else if self.repr.starts_with("<td") {
+			format!("<table><tbody><tr>{}</tr></tbody></table>", self.repr)
+		} else {
+			self.repr.clone()
+		};
+		let res = Node::new(repr.as_bytes());
+		if res.is_err() {
+			debug!("failed to create node from \"{}\"", self.repr);
+		}
+		res.unwrap()
+	}
+
+	pub fn _to_str(&self) -> &str {
+		&self.repr
+	}
+}
+
+impl Element for WNode {
+	fn select(&self, selector: &str) -> Vec<Self> {
+		WNode::select(self, selector)
+	}
+
+	fn attr(&self, name: &str) -> Option<String> {
+		WNode::attr(self, name)
+	}
+
+	fn text(&self) -> String {
+		WNode::text(self)
+	}
+
+	fn data(&self) -> String {
+		WNode::data(self)
+	}
+}
@@ -0,0 +1,14 @@
+use aidoku::std::current_date;
+use common::dates;
+
+pub fn parse_chapter_date(date_str: &str) -> Option<f64> {
+	let date_str = date_str.trim().to_lowercase();
+	if date_str.is_empty() {
+		return None;
+	}
+
+	dates::parse_numeric_date(&date_str, "dd.MM.yyyy")
+		.or_else(|| dates::parse_numeric_date(&date_str, "dd-MM-yyyy"))
+		.or_else(|| dates::parse_month_name_date(&date_str, current_date()))
+		.or_else(|| dates::parse_relative_date(&date_str, current_date()))
+}
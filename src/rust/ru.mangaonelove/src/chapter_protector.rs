@@ -0,0 +1,25 @@
+use aidoku::{
+	prelude::*,
+	std::{String, Vec},
+};
+use common::madara_protector;
+
+use crate::wrappers::{debug, WNode};
+
+// Chapters with the protector enabled have no images in the markup, the urls are in an
+// encrypted JSON array instead
+pub fn get_protected_image_urls(html: &WNode) -> Option<Vec<String>> {
+	let script = madara_protector::find_protector_script(html)?;
+	let urls = madara_protector::decrypt_image_urls(&script);
+	if urls.is_none() {
+		debug!("failed to decrypt protected chapter data");
+	}
+	urls
+}
+
+// Some versions of the protector keep the list unencrypted in `chapter_preloaded_images`
+pub fn get_preloaded_image_urls(html: &WNode) -> Option<Vec<String>> {
+	html.select("script")
+		.into_iter()
+		.find_map(|script_node| madara_protector::parse_preloaded_image_urls(&script_node.data()))
+}
@@ -1,11 +1,20 @@
 use aidoku::std::defaults::defaults_get;
-use aidoku::{prelude::*, Manga, MangaPageResult, MangaStatus};
+use aidoku::{prelude::*, Filter, Manga, MangaContentRating, MangaPageResult, MangaStatus};
 use alloc::{
 	string::{String, ToString},
 	vec::Vec,
 };
 
-use crate::constants::{MANGA_DIR, SEARCH_OFFSET_STEP};
+use common::{madara_search_form, search_form};
+
+use crate::{
+	constants::{MANGA_DIR, PAGE_DIR, SEARCH_OFFSET_STEP},
+	sorting::Sorting,
+	wrappers::{debug, get_html},
+};
+
+// labels and slugs of the search form genres, loaded once per session
+static mut GENRE_SLUGS: Option<Vec<(String, String)>> = None;
 
 pub fn show_nsfw() -> bool {
 	defaults_get("showNsfw")
@@ -19,6 +28,15 @@ pub fn show_only_nsfw() -> bool {
 		.unwrap_or_default()
 }
 
+fn allow_manga(manga: &Manga) -> bool {
+	let is_nsfw = manga.nsfw == MangaContentRating::Nsfw;
+	if show_only_nsfw() && show_nsfw() {
+		is_nsfw
+	} else {
+		show_nsfw() || !is_nsfw
+	}
+}
+
 pub fn get_base_url() -> String {
 	defaults_get("baseUrl")
 		.and_then(|x| x.as_string())
@@ -33,6 +51,43 @@ pub fn get_manga_base_url() -> String {
 	format!("{}/{}", get_base_url(), MANGA_DIR)
 }
 
+pub fn get_catalogue_url(sorting: &Sorting, page: i32) -> String {
+	format!(
+		"{}/{PAGE_DIR}/{page}/?m_orderby={sorting}",
+		get_manga_base_url()
+	)
+}
+
+fn get_search_form_url() -> String {
+	format!("{}/?s=&post_type=wp-manga", get_base_url())
+}
+
+fn load_genre_slugs() -> Option<&'static [(String, String)]> {
+	// sources are called from a single thread
+	let genre_slugs = unsafe { &mut *core::ptr::addr_of_mut!(GENRE_SLUGS) };
+	if genre_slugs.is_none() {
+		let html = get_html(&get_search_form_url()).ok()?;
+		*genre_slugs = Some(madara_search_form::parse_genre_slugs(&html))
+			.filter(|genre_slugs| !genre_slugs.is_empty());
+	}
+	genre_slugs.as_deref()
+}
+
+// Slug of a genre as the site's search form names it. The filter list carries no slugs, a
+// guessed one would make the site ignore the genre without telling, so the genre is skipped
+// while the form can't be loaded
+pub fn get_genre_slug(filter: &Filter) -> Option<String> {
+	let Some(genre_slugs) = load_genre_slugs() else {
+		debug!("failed to load the search form, genre \"{}\" is skipped", filter.name);
+		return None;
+	};
+	let slug = search_form::find_by_label(genre_slugs, &filter.name);
+	if slug.is_none() {
+		debug!("genre \"{}\" is not offered by the site", filter.name);
+	}
+	slug.map(ToString::to_string)
+}
+
 pub fn get_manga_url(id: &str) -> String {
 	format!("{}/{id}", get_manga_base_url())
 }
@@ -61,10 +116,12 @@ pub fn get_manga_id(url: &str) -> Option<String> {
 	Some(manga_id.to_string())
 }
 
+// `has_more` has to be decided before NSFW filtering, otherwise a single hidden title would
+// end the pagination
 pub fn create_manga_page_result(mangas: Vec<Manga>, has_more: Option<bool>) -> MangaPageResult {
-	let has_more = has_more.unwrap_or(mangas.len() == SEARCH_OFFSET_STEP as usize);
+	let has_more = has_more.unwrap_or(mangas.len() >= SEARCH_OFFSET_STEP as usize);
 	MangaPageResult {
-		manga: mangas,
+		manga: mangas.into_iter().filter(allow_manga).collect(),
 		has_more,
 	}
 }
@@ -2,9 +2,12 @@
 #![feature(pattern)]
 #![feature(iter_intersperse)]
 
+mod chapter_date;
+mod chapter_protector;
 mod constants;
 mod helpers;
 mod parser;
+mod sorting;
 mod wrappers;
 
 use aidoku::{
@@ -13,6 +16,7 @@ use aidoku::{
 	std::{String, Vec},
 	Chapter, DeepLink, Filter, Listing, Manga, MangaPageResult, Page,
 };
+use sorting::Sorting;
 
 extern crate alloc;
 
@@ -21,14 +25,17 @@ pub fn get_manga_list(filters: Vec<Filter>, page: i32) -> Result<MangaPageResult
 	let search_url = parser::get_filter_url(&filters, page).ok_or(constants::PARSING_ERROR)?;
 	let html = wrappers::get_html(&search_url)?;
 	let mangas = parser::parse_search_results(&html).ok_or(constants::PARSING_ERROR)?;
-	Ok(helpers::create_manga_page_result(mangas, None))
+	let has_more = parser::parse_has_more(&html);
+	Ok(helpers::create_manga_page_result(mangas, has_more))
 }
 
 #[get_manga_listing]
-pub fn get_manga_listing(listing: Listing, _page: i32) -> Result<MangaPageResult> {
-	let html = wrappers::get_html(&helpers::get_base_url())?;
-	let mangas = parser::parse_lising(&html, listing).ok_or(constants::PARSING_ERROR)?;
-	Ok(helpers::create_manga_page_result(mangas, Some(false)))
+pub fn get_manga_listing(listing: Listing, page: i32) -> Result<MangaPageResult> {
+	let url = helpers::get_catalogue_url(&Sorting::from_listing(&listing), page);
+	let html = wrappers::get_html(&url)?;
+	let mangas = parser::parse_search_results(&html).ok_or(constants::PARSING_ERROR)?;
+	let has_more = parser::parse_has_more(&html);
+	Ok(helpers::create_manga_page_result(mangas, has_more))
 }
 
 #[get_manga_details]
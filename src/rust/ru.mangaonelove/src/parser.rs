@@ -1,172 +1,130 @@
 use aidoku::{
 	helpers::{substring::Substring, uri::encode_uri},
 	prelude::*,
-	std::{current_date, String, StringRef, Vec},
-	Chapter, Filter, FilterType, Listing, Manga, MangaContentRating, MangaViewer, Page,
+	std::{String, Vec},
+	Chapter, Filter, FilterType, Manga, MangaContentRating, MangaStatus, MangaViewer, Page,
 };
 
 extern crate alloc;
 use alloc::string::ToString;
 
+use common::{
+	chapter_label::{self, parse_chapter_label},
+	madara_chapters,
+};
+
 use crate::{
+	chapter_date::parse_chapter_date,
+	chapter_protector::{get_preloaded_image_urls, get_protected_image_urls},
 	constants::PAGE_DIR,
-	helpers::{get_base_url, get_manga_id, get_manga_url, parse_status, show_nsfw, show_only_nsfw},
+	helpers::{get_base_url, get_genre_slug, get_manga_id, get_manga_url, parse_status},
+	sorting::Sorting,
 	wrappers::{post, WNode},
 };
 
-pub fn parse_lising(html: &WNode, listing: Listing) -> Option<Vec<Manga>> {
-	let sidebar_class = match listing.name.as_str() {
-		"Популярное" => "c-top-sidebar",
-		"Новое" => "c-top-second-sidebar",
-		_ => return None,
-	};
-
-	let sidebar_node = html.select_one(&format!("div.c-sidebar.{sidebar_class}"))?;
+// Search results and catalogue pages differ only in the wrapping classes, the catalogue items
+// lack the details block, so their status is unknown
+fn parse_manga_item(manga_node: &WNode, thumb_node: &WNode, summary_node: &WNode) -> Option<Manga> {
+	let title_link_node = summary_node.select_one("div.post-title a")?;
+	let content_node = summary_node.select_one("div.post-content");
 
-	let allow_manga = |rating: &MangaContentRating| -> bool {
-		if show_only_nsfw() {
-			if show_nsfw() {
-				*rating == MangaContentRating::Nsfw
-			} else {
-				*rating != MangaContentRating::Nsfw
-			}
-		} else if show_nsfw() {
-			true
-		} else {
-			*rating != MangaContentRating::Nsfw
-		}
+	let extract_from_content = |class_name| {
+		content_node
+			.as_ref()?
+			.select_one(&format!("div.{class_name}"))?
+			.select_one("div.summary-content")
+			.map(|n| n.text())
 	};
 
-	let mangas = sidebar_node
-		.select("div.slider__item")
+	let url = title_link_node.attr("href")?;
+	let id = get_manga_id(&url)?;
+	let img_node = thumb_node.select_one("img")?;
+	let cover = img_node.attr("data-src").or_else(|| img_node.attr("src"))?;
+	let title = title_link_node.text().trim().to_string();
+	let author = extract_from_content("mg_author").unwrap_or_default();
+	let artist = extract_from_content("mg_artists").unwrap_or_default();
+	let categories: Vec<String> = content_node
+		.as_ref()
+		.map(|content_node| {
+			content_node
+				.select("div.mg_genres a")
+				.iter()
+				.map(WNode::text)
+				.collect()
+		})
+		.unwrap_or_default();
+	let status = extract_from_content("mg_status")
+		.map(|status| parse_status(&status))
+		.unwrap_or(MangaStatus::Unknown);
+	let has_adult_badge = manga_node
+		.select("span.manga-title-badges")
 		.iter()
-		.filter_map(|manga_node| {
-			let thumb_node = manga_node.select_one("div.slider__thumb_item")?;
-			let desc_node = manga_node.select_one("div.slider__content_item")?;
-
-			let thumb_link_node = thumb_node.select_one("a")?;
-			let url = thumb_link_node.attr("href")?;
-			let id = get_manga_id(&url)?;
-
-			let img_node = thumb_link_node.select_one("img")?;
-			let cover = match img_node.attr("data-src").or_else(|| img_node.attr("src")) {
-				Some(c) => c,
-				None => return None,
-			};
-
-			let title = desc_node.select_one("div.post-title")?.text();
+		.any(|badge_node| badge_node.text().contains("18+"));
+	let nsfw = if has_adult_badge
+		|| categories
+			.iter()
+			.any(|c| c.contains("18+") || c.contains("Взрослая"))
+	{
+		MangaContentRating::Nsfw
+	} else {
+		MangaContentRating::Suggestive
+	};
 
-			let nsfw = match thumb_node.select_one("span") {
-				Some(span_node) => {
-					if span_node.text().contains("18+") {
-						MangaContentRating::Nsfw
-					} else {
-						MangaContentRating::Suggestive
-					}
-				}
-				None => MangaContentRating::Safe,
-			};
+	Some(Manga {
+		id,
+		cover,
+		title,
+		author,
+		artist,
+		url,
+		categories,
+		status,
+		nsfw,
+		..Default::default()
+	})
+}
 
-			Some(Manga {
-				id,
-				cover,
-				title,
-				url,
-				nsfw,
-				..Default::default()
-			})
-		})
-		.filter(|m| allow_manga(&m.nsfw))
-		.collect();
+fn parse_search_item(manga_node: &WNode) -> Option<Manga> {
+	let thumb_node = manga_node.select_one("div.tab-thumb")?;
+	let summary_node = manga_node.select_one("div.tab-summary")?;
+	parse_manga_item(manga_node, &thumb_node, &summary_node)
+}
 
-	Some(mangas)
+// Catalogue pages (`/manga/page/N/`) show only the cover, the title and the age badge
+fn parse_catalogue_item(manga_node: &WNode) -> Option<Manga> {
+	let thumb_node = manga_node.select_one("div.item-thumb")?;
+	let summary_node = manga_node.select_one("div.item-summary")?;
+	parse_manga_item(manga_node, &thumb_node, &summary_node)
 }
 
+// NSFW settings are applied later, so pagination can rely on the full page
 pub fn parse_search_results(html: &WNode) -> Option<Vec<Manga>> {
-	let list_node = html
-		.select_one("div.c-page-content div.main-col-inner div.tab-content-wrap div.c-tabs-item")?;
-
-	let allow_manga = |rating: &MangaContentRating| -> bool {
-		if show_only_nsfw() {
-			if show_nsfw() {
-				*rating == MangaContentRating::Nsfw
-			} else {
-				*rating != MangaContentRating::Nsfw
-			}
-		} else if show_nsfw() {
-			true
-		} else {
-			*rating != MangaContentRating::Nsfw
-		}
-	};
-
-	let mangas = list_node
-		.select("div.c-tabs-item__content")
+	let search_items = html
+		.select("div.c-tabs-item div.c-tabs-item__content")
 		.into_iter()
-		.filter_map(|manga_node| {
-			let thumb_node = manga_node.select_one("div.tab-thumb")?;
-			let summary_node = manga_node.select_one("div.tab-summary")?;
-
-			let title_node = summary_node.select_one("div.post-title")?;
-			let content_node = summary_node.select_one("div.post-content")?;
-
-			let extract_from_content = |class_name| {
-				content_node
-					.select_one(&format!("div.{class_name}"))?
-					.select_one("div.summary-content")
-					.map(|n| n.text())
-			};
-
-			let title_link_node = title_node.select_one("a")?;
-			let url = title_link_node.attr("href")?;
-			let id = get_manga_id(&url)?;
-			let img_node = thumb_node.select_one("img")?;
-			let cover = match img_node.attr("data-src").or_else(|| img_node.attr("src")) {
-				Some(c) => c,
-				None => return None,
-			};
-			let title = title_link_node.text();
-			let author = extract_from_content("mg_author").unwrap_or_default();
-			let artist = extract_from_content("mg_artists").unwrap_or_default();
-			let categories: Vec<String> = content_node
-				.select("div.mg_genres a")
-				.iter()
-				.map(WNode::text)
-				.collect();
-			let status = parse_status(&extract_from_content("mg_status")?);
-			let nsfw = if categories
-				.iter()
-				.any(|c| c.contains("18+") || c.contains("Взрослая"))
-			{
-				MangaContentRating::Nsfw
-			} else {
-				MangaContentRating::Suggestive
-			};
-
-			let manga = Manga {
-				id,
-				cover,
-				title,
-				author,
-				artist,
-				url,
-				categories,
-				status,
-				nsfw,
-				..Default::default()
-			};
+		.filter_map(|manga_node| parse_search_item(&manga_node));
+	let catalogue_items = html
+		.select("div.page-listing-item div.page-item-detail")
+		.into_iter()
+		.filter_map(|manga_node| parse_catalogue_item(&manga_node));
 
-			if allow_manga(&manga.nsfw) {
-				Some(manga)
-			} else {
-				None
-			}
-		})
-		.collect();
+	let mangas = search_items.chain(catalogue_items).collect();
 
 	Some(mangas)
 }
 
+// Madara renders either numbered navigation or "older posts" link
+pub fn parse_has_more(html: &WNode) -> Option<bool> {
+	let navigation_node = html
+		.select_one("div.wp-pagenavi")
+		.or_else(|| html.select_one("div.nav-links"))?;
+	Some(
+		navigation_node.select_one("a.nextpostslink").is_some()
+			|| navigation_node.select_one("a.next").is_some()
+			|| navigation_node.select_one("div.nav-previous a").is_some(),
+	)
+}
+
 pub fn parse_manga(html: &WNode, id: String) -> Option<Manga> {
 	let main_node = html.select_one("div.profile-manga > div.container > div.row")?;
 	let description_node = html.select_one("div.c-page-content div.description-summary")?;
@@ -255,128 +213,109 @@ pub fn parse_manga(html: &WNode, id: String) -> Option<Manga> {
 	})
 }
 
-pub fn parse_chapters(html: &WNode, manga_id: &str) -> Option<Vec<Chapter>> {
-	// Prefer inline chapter list if present (as on example.manga.html),
-	// otherwise fallback to AJAX-loaded chapters
-	let chapter_nodes =
-		match html.select_one("div.page-content-listing.single-page ul.main.version-chap") {
-			Some(list) => list.select("li.wp-manga-chapter"),
-			None => {
-				let manga_chapters_holder_node =
-					html.select_one("div.c-page-content div#manga-chapters-holder")?;
-				let data_id = manga_chapters_holder_node.attr("data-id")?;
-				let real_manga_chapters_holder_node = post(
-					&format!("{}/wp-admin/admin-ajax.php", get_base_url()),
-					&format!("action=manga_get_chapters&manga={data_id}"),
-					&[
-						("X-Requested-With", "XMLHttpRequest"),
-						("Referer", &format!("{}", get_manga_url(manga_id))),
-					],
-				)
-				.ok()?;
-				real_manga_chapters_holder_node.select("ul > li.wp-manga-chapter")
-			}
-		};
+#[derive(Clone, Copy, PartialEq)]
+enum ChapterEndpoint {
+	// `POST {manga_url}/ajax/chapters/` of newer Madara versions
+	MangaAjax,
+	// `POST /wp-admin/admin-ajax.php` with `action=manga_get_chapters`
+	AdminAjax,
+}
 
-	let abs = |l, r| {
-		if l > r {
-			l - r
-		} else {
-			r - l
+// endpoint that returned chapters last time during this session, it is tried first
+static mut WORKING_CHAPTER_ENDPOINT: Option<ChapterEndpoint> = None;
+
+fn request_chapter_list(endpoint: ChapterEndpoint, html: &WNode, manga_id: &str) -> Option<WNode> {
+	let referer = get_manga_url(manga_id);
+	let headers = [
+		("X-Requested-With", "XMLHttpRequest"),
+		("Referer", referer.as_str()),
+	];
+	let response = match endpoint {
+		ChapterEndpoint::MangaAjax => post(&format!("{referer}/ajax/chapters/"), "", &headers),
+		ChapterEndpoint::AdminAjax => {
+			let data_id = madara_chapters::parse_manga_data_id(html)?;
+			post(
+				&format!("{}/wp-admin/admin-ajax.php", get_base_url()),
+				&format!("action=manga_get_chapters&manga={data_id}"),
+				&headers,
+			)
 		}
 	};
+	madara_chapters::find_ajax_chapter_list(&response.ok()?)
+}
+
+// Prefer inline chapter list if present (as on example.manga.html), otherwise the chapters
+// are loaded by one of the AJAX endpoints
+fn get_chapter_list_node(html: &WNode, manga_id: &str) -> Option<WNode> {
+	if let Some(list_node) = madara_chapters::find_inline_chapter_list(html) {
+		return Some(list_node);
+	}
+
+	// sources are called from a single thread
+	let working_endpoint = unsafe { &mut *core::ptr::addr_of_mut!(WORKING_CHAPTER_ENDPOINT) };
+
+	let mut endpoints = [ChapterEndpoint::MangaAjax, ChapterEndpoint::AdminAjax];
+	if *working_endpoint == Some(ChapterEndpoint::AdminAjax) {
+		endpoints.reverse();
+	}
+	endpoints.into_iter().find_map(|endpoint| {
+		let list_node = request_chapter_list(endpoint, html, manga_id)?;
+		*working_endpoint = Some(endpoint);
+		Some(list_node)
+	})
+}
+
+pub fn parse_chapters(html: &WNode, manga_id: &str) -> Option<Vec<Chapter>> {
+	let list_node = get_chapter_list_node(html, manga_id)?;
+
+	let rows = madara_chapters::parse_chapter_rows(&list_node);
 
-	let chapters = chapter_nodes
+	// the list goes from the newest chapter to the oldest one
+	let rows_count = rows.len();
+
+	let chapters: Vec<(Chapter, bool)> = rows
 		.into_iter()
 		.enumerate()
-		.filter_map(|(idx, chapter_node)| {
-			let url_node = chapter_node.select_one("a")?;
-			let url = url_node.attr("href")?;
+		.filter_map(|(idx, row)| {
+			let url = row.url;
 			let id = url
 				.substring_after(&format!("{}/", get_manga_url(manga_id)))?
 				.trim_end_matches('/')
 				.to_string();
-			let title = url_node.text();
-
-			let chapter = {
-				let approx_chapter = (idx + 1) as f32;
-				let mut possible_chapters: Vec<_> = title
-					.split_whitespace()
-					.filter_map(|word| word.parse::<f32>().ok())
-					.collect();
-				match possible_chapters[..] {
-					[] => approx_chapter,
-					[chap] => chap,
-					_ => {
-						possible_chapters.sort_by(|&l, &r| {
-							abs(l, approx_chapter)
-								.partial_cmp(&abs(r, approx_chapter))
-								.unwrap()
-						});
-						possible_chapters.first().cloned().unwrap_or(approx_chapter)
-					}
-				}
-			};
-
-			let extract_multiplier = |metric_str: &&str| {
-				if metric_str.starts_with("сек") {
-					Some(1)
-				} else if metric_str.starts_with("мин") {
-					Some(60)
-				} else if metric_str.starts_with("час") {
-					Some(60 * 60)
-				} else if metric_str.starts_with("дн") {
-					Some(24 * 60 * 60)
-				} else {
-					None
-				}
-			};
-
-			let date_updated = {
-				let release_date_node = chapter_node.select_one("span.chapter-release-date")?;
-				let normal_release_date = release_date_node.select_one("i").map(|i_node| {
-					let txt = i_node.text();
-					let parsed1 = StringRef::from(&txt).as_date("dd.MM.yyyy", None, None);
-					if parsed1 > 0f64 {
-						parsed1
-					} else {
-						StringRef::from(&txt).as_date("dd-MM-yyyy", None, None)
-					}
-				});
-
-				let ago_extractor = || {
-					release_date_node
-						.select_one("a")
-						.and_then(|a| a.attr("title"))
-						.and_then(|updated_text| {
-							if !updated_text.ends_with("ago") {
-								return None;
-							}
-							let spl: Vec<_> = updated_text.split_whitespace().collect();
-							let count = spl
-								.first()
-								.and_then(|count_str| count_str.parse::<f64>().ok())?;
-
-							let metric_mult = spl.get(1).and_then(extract_multiplier)?;
-
-							Some(current_date() - count * (metric_mult as f64))
-						})
-						.unwrap_or(0f64)
-				};
-
-				normal_release_date.unwrap_or_else(ago_extractor)
-			};
+			let label = parse_chapter_label(&row.label);
+			// position in the list is used only when the label has no number at all
+			let chapter = label.chapter.unwrap_or((rows_count - idx) as f32);
+			let date_updated = row
+				.release_dates
+				.iter()
+				.find_map(|release_date| parse_chapter_date(release_date))
+				.unwrap_or(0f64);
+			let volume = row.group_volume.or(label.volume).unwrap_or(-1.0);
 
-			// TODO: implement proper volume parsing
-			Some(Chapter {
+			let chapter = Chapter {
 				id,
-				title,
+				title: label.title,
+				volume,
 				chapter,
 				date_updated,
 				url,
 				lang: "ru".to_string(),
 				..Default::default()
-			})
+			};
+			Some((chapter, label.is_extra))
+		})
+		.collect();
+
+	let numbers: Vec<_> = chapters
+		.iter()
+		.map(|(chapter, is_extra)| (chapter.chapter, *is_extra))
+		.collect();
+	let chapters = chapters
+		.into_iter()
+		.zip(chapter_label::number_extras(&numbers))
+		.map(|((chapter, _), number)| Chapter {
+			chapter: number,
+			..chapter
 		})
 		.collect();
 
@@ -384,13 +323,23 @@ pub fn parse_chapters(html: &WNode, manga_id: &str) -> Option<Vec<Chapter>> {
 }
 
 pub fn get_page_list(html: &WNode) -> Option<Vec<Page>> {
-	let reader_content_node = html.select_one("div.read-container > div.reading-content")?;
-	let page_nodes = reader_content_node.select("div.page-break > img");
-	let urls: Vec<_> = page_nodes
-		.into_iter()
-		.filter_map(|img_node| img_node.attr("src"))
-		.map(|url| url.trim().to_string())
-		.collect();
+	let mut urls: Vec<_> = html
+		.select_one("div.read-container > div.reading-content")
+		.map(|reader_content_node| {
+			reader_content_node
+				.select("div.page-break > img")
+				.into_iter()
+				.filter_map(|img_node| img_node.attr("src"))
+				.map(|url| url.trim().to_string())
+				.collect()
+		})
+		.unwrap_or_default();
+
+	if urls.is_empty() {
+		urls = get_protected_image_urls(html)
+			.or_else(|| get_preloaded_image_urls(html))
+			.unwrap_or_default();
+	}
 
 	Some(
 		urls.into_iter()
@@ -404,28 +353,71 @@ pub fn get_page_list(html: &WNode) -> Option<Vec<Page>> {
 	)
 }
 
+fn get_filter_id(filter: &Filter) -> Option<String> {
+	filter.object.get("id").as_string().ok().map(|id| id.read())
+}
+
 pub fn get_filter_url(filters: &[Filter], page: i32) -> Option<String> {
-	const QUERY_PART: &str = "&s=";
+	let mut query = String::new();
+	let mut sorting = Sorting::default();
+	let mut params: Vec<String> = Vec::new();
 
-	let filter_addition: String = filters
-		.iter()
-		.filter_map(|filter| match filter.kind {
+	for filter in filters {
+		match filter.kind {
 			FilterType::Title => {
-				let value = filter.value.clone().as_string().ok()?.read();
-				Some(format!("{QUERY_PART}{}", encode_uri(value)))
+				if let Ok(title_ref) = filter.value.clone().as_string() {
+					query = encode_uri(title_ref.read());
+				}
 			}
-			_ => None,
-		})
-		.collect();
+			FilterType::Author => {
+				if let Ok(author_ref) = filter.value.clone().as_string() {
+					params.push(format!("author={}", encode_uri(author_ref.read())));
+				}
+			}
+			FilterType::Text => {
+				let Ok(value_ref) = filter.value.clone().as_string() else {
+					continue;
+				};
+				let key = match get_filter_id(filter).as_deref() {
+					Some(key @ ("artist" | "release")) => key.to_string(),
+					_ => continue,
+				};
+				params.push(format!("{key}={}", encode_uri(value_ref.read())));
+			}
+			FilterType::Select => {
+				let index = filter.value.as_int().unwrap_or(0);
+				match get_filter_id(filter).as_deref() {
+					// Madara treats any non empty `op` as "all of the selected genres"
+					Some("op") if index == 1 => params.push("op=1".to_string()),
+					Some("adult") if index > 0 => params.push(format!("adult={}", index - 1)),
+					_ => {}
+				}
+			}
+			FilterType::Sort => sorting = Sorting::from_filter(filter),
+			FilterType::Genre => {
+				if filter.value.as_int().unwrap_or(-1) != 1 {
+					continue;
+				}
+				if let Some(slug) = get_genre_slug(filter) {
+					params.push(format!("genre[]={slug}"));
+				}
+			}
+			FilterType::Check => {
+				if filter.value.as_int().unwrap_or(0) == 0 {
+					continue;
+				}
+				if let Some(id) = get_filter_id(filter) {
+					params.push(format!("status[]={id}"));
+				}
+			}
+			_ => {}
+		}
+	}
 
-	let filter_addition = match filter_addition.find(QUERY_PART) {
-		Some(_) => filter_addition,
-		None => filter_addition + QUERY_PART,
-	};
+	let params: String = params.iter().map(|param| format!("&{param}")).collect();
 
 	Some(format!(
-		"{}/{PAGE_DIR}/{page}/?post_type=wp-manga&m_orderby=latest{}",
+		"{}/{PAGE_DIR}/{page}/?s={query}&post_type=wp-manga&m_orderby={sorting}{params}",
 		get_base_url(),
-		filter_addition
 	))
 }
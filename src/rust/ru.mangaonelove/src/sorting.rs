@@ -0,0 +1,57 @@
+use core::fmt::Display;
+
+use aidoku::{Filter, Listing};
+
+#[derive(Debug, Default)]
+pub enum Sorting {
+	#[default]
+	Latest,
+	Alphabet,
+	Rating,
+	Trending,
+	Views,
+	NewManga,
+}
+
+impl Sorting {
+	pub fn from_listing(listing: &Listing) -> Self {
+		match listing.name.as_str() {
+			"Популярное" => Self::Views,
+			"Новое" => Self::NewManga,
+			"Тренды" => Self::Trending,
+			"Рейтинг" => Self::Rating,
+			_ => Self::Latest,
+		}
+	}
+
+	// options go in the same order as in `res/filters.json`
+	pub fn from_filter(filter: &Filter) -> Self {
+		let index = filter
+			.value
+			.clone()
+			.as_object()
+			.and_then(|value| value.get("index").as_int())
+			.unwrap_or(0);
+		match index {
+			1 => Self::Alphabet,
+			2 => Self::Rating,
+			3 => Self::Trending,
+			4 => Self::Views,
+			5 => Self::NewManga,
+			_ => Self::Latest,
+		}
+	}
+}
+
+impl Display for Sorting {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Sorting::Latest => write!(f, "latest"),
+			Sorting::Alphabet => write!(f, "alphabet"),
+			Sorting::Rating => write!(f, "rating"),
+			Sorting::Trending => write!(f, "trending"),
+			Sorting::Views => write!(f, "views"),
+			Sorting::NewManga => write!(f, "new-manga"),
+		}
+	}
+}
@@ -105,6 +105,10 @@ impl WNode {
 		self.to_node().text().read()
 	}
 
+	pub fn data(&self) -> String {
+		self.to_node().data().read()
+	}
+
 	fn to_node(&self) -> Node {
 		let res = Node::new(self.repr.as_bytes());
 		if res.is_err() {
@@ -117,3 +121,21 @@ impl WNode {
 		&self.repr
 	}
 }
+
+impl common::dom::Element for WNode {
+	fn select(&self, selector: &str) -> Vec<Self> {
+		WNode::select(self, selector)
+	}
+
+	fn attr(&self, attr: &str) -> Option<String> {
+		WNode::attr(self, attr)
+	}
+
+	fn text(&self) -> String {
+		WNode::text(self)
+	}
+
+	fn data(&self) -> String {
+		WNode::data(self)
+	}
+}
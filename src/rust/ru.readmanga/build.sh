@@ -1,7 +0,0 @@
-rm -f package.aix
-cargo +nightly build --release
-mkdir -p target/wasm32-unknown-unknown/release/Payload
-cp -av res/* target/wasm32-unknown-unknown/release/Payload
-cp target/wasm32-unknown-unknown/release/*.wasm target/wasm32-unknown-unknown/release/Payload/main.wasm
-cd target/wasm32-unknown-unknown/release ; zip -r package.aix Payload
-mv package.aix ../../../package.aix
@@ -1 +0,0 @@
-pub const SEARCH_OFFSET_STEP: i32 = 50;